@@ -0,0 +1,80 @@
+//! Stands in for a game server when testing the ahelp relay locally.
+//!
+//! Prints staff replies the bot sends to `ahelp.callback_url` and forwards every line typed
+//! into stdin to the bot as an ahelp of a fake player.
+//!
+//! ```sh
+//! cargo run --example ahelp_stub -- 127.0.0.1:1212 http://127.0.0.1:8080 <http.token>
+//! ```
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::Response;
+use hyper_util::rt::TokioIo;
+use std::convert::Infallible;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::net::TcpListener;
+
+const PLAYER_ID: &str = "00000000-0000-0000-0000-000000000001";
+const PLAYER_LOGIN: &str = "StubPlayer";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let listen = args.next().unwrap_or_else(|| "127.0.0.1:1212".to_string());
+    let bot_url = args
+        .next()
+        .unwrap_or_else(|| "http://127.0.0.1:8080".to_string());
+    let token = args.next().unwrap_or_default();
+
+    let listener = TcpListener::bind(&listen).await?;
+    println!(
+        "Listening for replies on {}, type messages to send ahelps",
+        listen
+    );
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+
+            tokio::spawn(async move {
+                let service = service_fn(|request: hyper::Request<hyper::body::Incoming>| async {
+                    let body = request
+                        .into_body()
+                        .collect()
+                        .await
+                        .map(|b| b.to_bytes())
+                        .unwrap_or_default();
+                    println!("< {}", String::from_utf8_lossy(&body));
+                    Ok::<_, Infallible>(Response::new(Full::new(Bytes::new())))
+                });
+
+                let _ = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await;
+            });
+        }
+    });
+
+    let client = reqwest::Client::new();
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        let response = client
+            .post(format!("{}/ahelp", bot_url.trim_end_matches('/')))
+            .bearer_auth(&token)
+            .json(&serde_json::json!({
+                "user_id": PLAYER_ID,
+                "login": PLAYER_LOGIN,
+                "message": line,
+            }))
+            .send()
+            .await?;
+        println!("> {}", response.status());
+    }
+
+    Ok(())
+}
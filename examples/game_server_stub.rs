@@ -0,0 +1,138 @@
+//! Stands in for an SS14 game server when testing `/status` and `/server` commands locally.
+//!
+//! Serves `/status`, `/admin/info` and `/admin/actions/*`, checks the
+//! `Authorization: SS14Token <token>` and `Actor` headers and prints every admin action.
+//!
+//! ```sh
+//! cargo run --example game_server_stub -- 127.0.0.1:1212 <admin_token>
+//! ```
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+const ADMIN_ACTIONS: &[&str] = &[
+    "/admin/actions/announce",
+    "/admin/actions/kick",
+    "/admin/actions/round/start",
+    "/admin/actions/round/end",
+    "/admin/actions/round/restartnow",
+];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let listen = args.next().unwrap_or_else(|| "127.0.0.1:1212".to_string());
+    let token = Arc::new(args.next().unwrap_or_default());
+
+    let listener = TcpListener::bind(&listen).await?;
+    println!("Serving game server API on {}", listen);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let token = Arc::clone(&token);
+
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let token = Arc::clone(&token);
+                async move { Ok::<_, Infallible>(route(&token, request).await) }
+            });
+
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+async fn route(token: &str, request: Request<Incoming>) -> Response<Full<Bytes>> {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+
+    if method == Method::GET && path == "/status" {
+        return respond(
+            StatusCode::OK,
+            json!({
+                "name": "Stub",
+                "players": 12,
+                "soft_max_players": 60,
+                "map": "Stub Station",
+                "round_id": 42,
+                "run_level": 1,
+            }),
+        );
+    }
+
+    if !path.starts_with("/admin/") {
+        return respond(StatusCode::NOT_FOUND, Value::Null);
+    }
+
+    let authorization = request
+        .headers()
+        .get(hyper::header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("SS14Token "));
+    if token.is_empty() || authorization != Some(token) {
+        println!("! {} {} rejected: bad SS14Token", method, path);
+        return respond(StatusCode::UNAUTHORIZED, Value::Null);
+    }
+
+    let actor = request
+        .headers()
+        .get("Actor")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| serde_json::from_str::<Value>(h).ok())
+        .filter(|a| a.get("Guid").is_some() && a.get("Name").is_some());
+    let Some(actor) = actor else {
+        println!("! {} {} rejected: missing or malformed Actor", method, path);
+        return respond(StatusCode::BAD_REQUEST, Value::Null);
+    };
+
+    match (method, path.as_str()) {
+        (Method::GET, "/admin/info") => respond(
+            StatusCode::OK,
+            json!({
+                "Players": [],
+                "GameRules": [],
+                "Panic": { "Enabled": false },
+                "RoundId": 42,
+                "Map": { "Id": "Stub", "Name": "Stub Station" },
+                "MOTD": null,
+            }),
+        ),
+        (Method::POST, path) if ADMIN_ACTIONS.contains(&path) => {
+            let body = request
+                .into_body()
+                .collect()
+                .await
+                .map(|b| b.to_bytes())
+                .unwrap_or_default();
+            println!(
+                "> {} by {}: {}",
+                path,
+                actor,
+                String::from_utf8_lossy(&body)
+            );
+            respond(StatusCode::OK, Value::Null)
+        }
+        _ => respond(StatusCode::NOT_FOUND, Value::Null),
+    }
+}
+
+fn respond(status: StatusCode, body: Value) -> Response<Full<Bytes>> {
+    let body = match body {
+        Value::Null => Bytes::new(),
+        body => Bytes::from(body.to_string()),
+    };
+
+    let mut response = Response::new(Full::new(body));
+    *response.status_mut() = status;
+    response
+}
@@ -0,0 +1,5 @@
+-- Last processed row IDs of SS14 database tables polled by background tasks
+CREATE TABLE IF NOT EXISTS high_water_marks (
+    name TEXT PRIMARY KEY NOT NULL,
+    value INTEGER NOT NULL
+);
@@ -0,0 +1,8 @@
+-- Player counts of game servers recorded by the status poller
+CREATE TABLE IF NOT EXISTS player_count_samples (
+    server TEXT NOT NULL,
+    time INTEGER NOT NULL,
+    players INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS player_count_samples_time ON player_count_samples (time);
@@ -0,0 +1,7 @@
+-- Discord forum threads relaying ahelps of SS14 players
+CREATE TABLE IF NOT EXISTS ahelp_threads (
+    user_id TEXT PRIMARY KEY NOT NULL,
+    thread_id INTEGER NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS ahelp_threads_thread_id ON ahelp_threads (thread_id);
@@ -1,16 +1,22 @@
 pub mod commands;
+pub mod endpoints;
+pub mod tasks;
 
 use crate::bot::commands::{DiscordCommandHandler, DiscordCommandResponse};
-use crate::services::ServicesContainer;
+use crate::bot::endpoints::DiscordEndpointHandler;
+use crate::bot::tasks::DiscordTaskHandler;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
 use crate::{config_get, config_get_array, error::Error};
 use log::{debug, error, info};
 use serenity::all::{
-    Command, CreateCommand, CreateInteractionResponse, CreateInteractionResponseMessage, GuildId,
-    Interaction, Ready,
+    Command, CommandInteraction, CreateAutocompleteResponse, CreateCommand,
+    CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
+    GatewayError, GuildId, Interaction, Message, Ready, UserId,
 };
 use serenity::async_trait;
 use serenity::prelude::*;
 use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 pub struct DiscordApp {
@@ -20,6 +26,14 @@ pub struct DiscordApp {
 
     handlers_map: BTreeMap<String, Arc<dyn DiscordCommandHandler + Send + Sync>>,
     handlers: Vec<Arc<dyn DiscordCommandHandler + Send + Sync>>,
+
+    tasks: Vec<Arc<dyn DiscordTaskHandler + Send + Sync>>,
+    tasks_started: AtomicBool,
+
+    endpoints: Vec<Arc<dyn DiscordEndpointHandler + Send + Sync>>,
+
+    ss14_client: Arc<SS14AuthClientService>,
+    ss14_db: Arc<SS14DatabaseService>,
 }
 
 #[async_trait]
@@ -58,112 +72,204 @@ impl EventHandler for DiscordApp {
 
         debug!("Registered {} global commands", commands.unwrap().len());
         info!("Finished commands registering. Listening for incoming interactions...");
+
+        // ready is fired again on reconnects, tasks must be spawned only once
+        if !self.tasks_started.swap(true, Ordering::SeqCst) {
+            self.spawn_tasks(&ctx);
+            self.spawn_endpoints(&ctx);
+        }
     }
 
-    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Command(cmd) = interaction {
-            debug!(
-                "Received `command` interaction from {}, command: {}. Processing...",
-                cmd.user.name, cmd.data.name
-            );
+    async fn message(&self, ctx: Context, message: Message) {
+        if message.guild_id.is_none() {
+            return;
+        }
+
+        for endpoint in &self.endpoints {
+            endpoint.message(&ctx, &message).await;
+        }
+    }
 
-            // check for allowed guilds
-            if let Some(guild_id) = cmd.guild_id {
-                if !self.guilds.contains(&guild_id) {
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        match interaction {
+            Interaction::Command(cmd) => {
+                debug!(
+                    "Received `command` interaction from {}, command: {}. Processing...",
+                    cmd.user.name, cmd.data.name
+                );
+
+                if let Some(denial) = self.check_guild(cmd.guild_id) {
                     if let Err(e) = cmd
                         .create_response(
                             &ctx.http,
                             CreateInteractionResponse::Message(
-                                CreateInteractionResponseMessage::new()
-                                    .content("Not allowed at this guild."),
+                                CreateInteractionResponseMessage::new().content(denial),
                             ),
                         )
                         .await
                     {
                         error!("Error sending followup command: {e}");
                     }
+                    return;
                 }
-            }
 
-            if cmd.guild_id.is_none() {
-                if let Err(e) = cmd
-                    .create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::Message(
-                            CreateInteractionResponseMessage::new().content("Not allowed in DMs."),
-                        ),
-                    )
-                    .await
-                {
-                    error!("Error sending followup command: {e}");
-                }
-            }
+                let handler = self.handlers_map.get(&cmd.data.name);
+                if handler.is_none() {
+                    let result = cmd
+                        .create_response(
+                            &ctx.http,
+                            CreateInteractionResponse::Message(
+                                CreateInteractionResponseMessage::new()
+                                    .content("Command not found!"),
+                            ),
+                        )
+                        .await;
+
+                    if let Err(e) = result {
+                        error!("Error responding to discord: {e}");
+                    }
 
-            let handler = self.handlers_map.get(&cmd.data.name);
-            if handler.is_none() {
-                let result = cmd
-                    .create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::Message(
-                            CreateInteractionResponseMessage::new().content("Command not found!"),
-                        ),
-                    )
-                    .await;
-
-                if let Err(e) = result {
-                    error!("Error responding to discord: {e}");
+                    return;
                 }
 
-                return;
-            }
+                let handler = handler.unwrap();
 
-            let handler = handler.unwrap();
-
-            // 2 branches
-            // deferred and default
-            if handler.definition().is_deferred {
-                if let Err(e) = cmd
-                    .create_response(
-                        &ctx.http,
-                        CreateInteractionResponse::Defer(
-                            CreateInteractionResponseMessage::new()
-                                .content("Your request is processing...")
-                                .ephemeral(handler.definition().is_ephemeral),
-                        ),
-                    )
-                    .await
-                {
-                    error!("Error creating defer response: {e}");
+                // 2 branches
+                // deferred and default
+                if handler.definition().is_deferred {
+                    if let Err(e) = cmd
+                        .create_response(
+                            &ctx.http,
+                            CreateInteractionResponse::Defer(
+                                CreateInteractionResponseMessage::new()
+                                    .content("Your request is processing...")
+                                    .ephemeral(handler.definition().is_ephemeral),
+                            ),
+                        )
+                        .await
+                    {
+                        error!("Error creating defer response: {e}");
+                        return;
+                    }
+
+                    if let Some(denial) =
+                        self.check_admin_flags(cmd.user.id, handler.as_ref()).await
+                    {
+                        let response = CreateInteractionResponseFollowup::new()
+                            .content(denial)
+                            .ephemeral(true);
+                        if let Err(e) = cmd.create_followup(&ctx.http, response).await {
+                            error!("Error sending followup command: {e}");
+                        }
+                        return;
+                    }
+
+                    let opts = &cmd.data.options();
+                    let response = handler.handler(&ctx, &cmd, opts).await;
+                    match response {
+                        DiscordCommandResponse::Default(_) => {
+                            error!("Deferred command returned default response!");
+                        }
+                        DiscordCommandResponse::Followup(response) => {
+                            if let Err(e) = cmd.create_followup(&ctx.http, response).await {
+                                error!("Error sending followup command: {e}");
+                            }
+                        }
+                    }
+                    return;
+                }
+
+                if let Some(denial) = self.check_admin_flags(cmd.user.id, handler.as_ref()).await {
+                    let response = CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(denial)
+                            .ephemeral(true),
+                    );
+                    if let Err(e) = cmd.create_response(&ctx.http, response).await {
+                        error!("Error responding to discord: {e}");
+                    }
                     return;
                 }
 
                 let opts = &cmd.data.options();
-                let response = handler.handler(opts).await;
+                let response = handler.handler(&ctx, &cmd, opts).await;
                 match response {
-                    DiscordCommandResponse::Default(_) => {
-                        error!("Deferred command returned default response!");
-                    }
-                    DiscordCommandResponse::Followup(response) => {
-                        if let Err(e) = cmd.create_followup(&ctx.http, response).await {
+                    DiscordCommandResponse::Default(response) => {
+                        if let Err(e) = cmd.create_response(&ctx.http, response).await {
                             error!("Error sending followup command: {e}");
                         }
                     }
+                    DiscordCommandResponse::Followup(_) => {
+                        error!("Default command returned deferred response!");
+                    }
                 }
-                return;
             }
+            Interaction::Autocomplete(cmd) => self.handle_autocomplete(&ctx, &cmd).await,
+            Interaction::Component(component) => {
+                let Some(handler) = self.handler_for_custom_id(&component.data.custom_id) else {
+                    error!(
+                        "No handler found for component: {}",
+                        component.data.custom_id
+                    );
+                    return;
+                };
 
-            let opts = &cmd.data.options();
-            let response = handler.handler(opts).await;
-            match response {
-                DiscordCommandResponse::Default(response) => {
-                    if let Err(e) = cmd.create_response(&ctx.http, response).await {
-                        error!("Error sending followup command: {e}");
+                // buttons act on behalf of their command and need the same permissions
+                if let Some(denial) = self
+                    .check_interaction(component.guild_id, component.user.id, handler.as_ref())
+                    .await
+                {
+                    let response = CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(denial)
+                            .ephemeral(true),
+                    );
+                    if let Err(e) = component.create_response(&ctx.http, response).await {
+                        error!("Error responding to component interaction: {e}");
                     }
+                    return;
                 }
-                DiscordCommandResponse::Followup(_) => {
-                    error!("Default command returned deferred response!");
+
+                let Some(response) = handler.component(&ctx, &component).await else {
+                    debug!("Component {} got no response", component.data.custom_id);
+                    return;
+                };
+
+                if let Err(e) = component.create_response(&ctx.http, response).await {
+                    error!("Error responding to component interaction: {e}");
                 }
             }
+            Interaction::Modal(modal) => {
+                let Some(handler) = self.handler_for_custom_id(&modal.data.custom_id) else {
+                    error!("No handler found for modal: {}", modal.data.custom_id);
+                    return;
+                };
+
+                if let Some(denial) = self
+                    .check_interaction(modal.guild_id, modal.user.id, handler.as_ref())
+                    .await
+                {
+                    let response = CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(denial)
+                            .ephemeral(true),
+                    );
+                    if let Err(e) = modal.create_response(&ctx.http, response).await {
+                        error!("Error responding to modal submit: {e}");
+                    }
+                    return;
+                }
+
+                let Some(response) = handler.modal(&ctx, &modal).await else {
+                    debug!("Modal {} got no response", modal.data.custom_id);
+                    return;
+                };
+
+                if let Err(e) = modal.create_response(&ctx.http, response).await {
+                    error!("Error responding to modal submit: {e}");
+                }
+            }
+            _ => {}
         }
     }
 }
@@ -171,7 +277,9 @@ impl EventHandler for DiscordApp {
 impl DiscordApp {
     pub fn new(
         command_defs: Vec<Arc<dyn DiscordCommandHandler + Send + Sync>>,
-        _services: &ServicesContainer,
+        task_defs: Vec<Arc<dyn DiscordTaskHandler + Send + Sync>>,
+        endpoint_defs: Vec<Arc<dyn DiscordEndpointHandler + Send + Sync>>,
+        services: &ServicesContainer,
     ) -> Result<Self, crate::error::Error> {
         let guilds: Vec<&str> = config_get_array!("discord.guilds", as_array, as_str).unwrap();
 
@@ -181,6 +289,11 @@ impl DiscordApp {
             guilds: Vec::with_capacity(guilds.len()),
             handlers: vec![],
             handlers_map: BTreeMap::new(),
+            tasks: task_defs,
+            tasks_started: AtomicBool::new(false),
+            endpoints: endpoint_defs,
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
         };
 
         app.construct_commands(command_defs)?;
@@ -193,14 +306,163 @@ impl DiscordApp {
     pub async fn start(self) -> Result<(), Error> {
         let token = config_get!("discord.token", as_str).unwrap();
 
-        let mut client = Client::builder(token, GatewayIntents::empty())
-            .event_handler(self)
-            .await?;
+        let intents = self
+            .endpoints
+            .iter()
+            .fold(GatewayIntents::empty(), |intents, e| intents | e.intents());
+
+        let mut client = Client::builder(token, intents).event_handler(self).await?;
+
+        if let Err(e) = client.start().await {
+            if let serenity::Error::Gateway(GatewayError::DisallowedGatewayIntents) = e {
+                error!(
+                    "Discord rejected gateway intents {:?}, enable them in the developer portal. See \"Privileged intents\" in README.md.",
+                    intents
+                );
+            }
+            return Err(e.into());
+        }
 
-        client.start().await?;
         Ok(())
     }
 
+    fn spawn_tasks(&self, ctx: &Context) {
+        for task in &self.tasks {
+            let task = Arc::clone(task);
+            let ctx = ctx.clone();
+
+            info!(
+                "Starting task {} with interval {:?}",
+                task.name(),
+                task.interval()
+            );
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(task.interval());
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+                loop {
+                    interval.tick().await;
+                    if let Err(e) = task.run(&ctx).await {
+                        error!("Task {} failed: {}", task.name(), e);
+                    }
+                }
+            });
+        }
+    }
+
+    fn spawn_endpoints(&self, ctx: &Context) {
+        if self.endpoints.is_empty() {
+            return;
+        }
+
+        let endpoints = self.endpoints.clone();
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = endpoints::serve(ctx, endpoints).await {
+                error!("HTTP server failed: {}", e);
+            }
+        });
+    }
+
+    /// Returns a message explaining why interactions from `guild_id` are not allowed, if they aren't.
+    fn check_guild(&self, guild_id: Option<GuildId>) -> Option<&'static str> {
+        match guild_id {
+            Some(guild_id) if self.guilds.contains(&guild_id) => None,
+            Some(_) => Some("Not allowed at this guild."),
+            None => Some("Not allowed in DMs."),
+        }
+    }
+
+    /// Applies the same guild and admin flag checks as the owning command to its other interactions.
+    async fn check_interaction(
+        &self,
+        guild_id: Option<GuildId>,
+        user: UserId,
+        handler: &(dyn DiscordCommandHandler + Send + Sync),
+    ) -> Option<String> {
+        if let Some(denial) = self.check_guild(guild_id) {
+            return Some(denial.to_string());
+        }
+
+        self.check_admin_flags(user, handler).await
+    }
+
+    /// Resolves the invoker to their SS14 account and checks the admin flags required by the command.
+    /// Returns a message explaining the denial, if any.
+    async fn check_admin_flags(
+        &self,
+        user: UserId,
+        handler: &(dyn DiscordCommandHandler + Send + Sync),
+    ) -> Option<String> {
+        let flags = handler.definition().admin_flags;
+        if flags.is_empty() {
+            return None;
+        }
+
+        let user_id = match self
+            .ss14_client
+            .get_user_id_from_discord(user.to_string())
+            .await
+        {
+            Ok(Some(user_id)) => user_id,
+            Ok(None) => return Some("🔍 Link your SS14 account to use this command.".to_string()),
+            Err(e) => {
+                error!("Failed to get UID of {} by Discord ID: {e}", user);
+                return Some("❌ An error occurred while checking your permissions.".to_string());
+            }
+        };
+
+        match self.ss14_db.has_admin_flags(user_id, flags).await {
+            Ok(true) => None,
+            Ok(false) => Some(format!(
+                "⛔ This command requires the {} flag(s) in game.",
+                flags.join(", ")
+            )),
+            Err(e) => {
+                error!("Failed to check admin flags of {user_id}: {e}");
+                Some("❌ An error occurred while checking your permissions.".to_string())
+            }
+        }
+    }
+
+    async fn handle_autocomplete(&self, ctx: &Context, cmd: &CommandInteraction) {
+        let Some(handler) = self.handlers_map.get(&cmd.data.name) else {
+            return;
+        };
+
+        let Some(focused) = cmd.data.autocomplete() else {
+            return;
+        };
+
+        // suggestions can leak player data, so they are only given to those allowed to run the command
+        let response = match self
+            .check_interaction(cmd.guild_id, cmd.user.id, handler.as_ref())
+            .await
+        {
+            Some(_) => CreateAutocompleteResponse::new(),
+            None => handler
+                .autocomplete(ctx, cmd, focused)
+                .await
+                .unwrap_or_default(),
+        };
+
+        if let Err(e) = cmd
+            .create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response))
+            .await
+        {
+            error!("Error sending autocomplete response: {e}");
+        }
+    }
+
+    /// Custom IDs of components and modals are prefixed by the name of the command that created them.
+    fn handler_for_custom_id(
+        &self,
+        custom_id: &str,
+    ) -> Option<&Arc<dyn DiscordCommandHandler + Send + Sync>> {
+        let name = custom_id.split(':').next()?;
+        self.handlers_map.get(name)
+    }
+
     fn construct_commands(
         &mut self,
         commands: Vec<Arc<dyn DiscordCommandHandler + Send + Sync>>,
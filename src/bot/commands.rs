@@ -1,24 +1,68 @@
+pub mod adminlog;
+pub mod adminsync;
+pub mod apply;
+pub mod ban;
+pub mod character;
+pub mod characters;
 pub mod femboy;
+pub mod leaderboard;
 pub mod link;
+pub mod notes;
 pub mod ping;
+pub mod player;
+pub mod playtime;
+pub mod roleban;
+pub mod round;
+pub mod rule;
+pub mod server;
+pub mod staff;
+pub mod stats;
+pub mod status;
 pub mod summon;
 pub mod user_id;
+pub mod watchdog;
+pub mod whitelist;
 
+pub use adminlog::AdminLogCommand;
+pub use adminsync::AdminSyncCommand;
+pub use apply::ApplyCommand;
+pub use ban::BanCommand;
+pub use character::CharacterCommand;
+pub use characters::CharactersCommand;
 pub use femboy::FemboyCommand;
+pub use leaderboard::LeaderboardCommand;
 pub use link::LinkCommand;
+pub use notes::NotesCommand;
 pub use ping::PingCommand;
+pub use player::PlayerCommand;
+pub use playtime::PlaytimeCommand;
+pub use roleban::RoleBanCommand;
+pub use round::RoundCommand;
+pub use rule::RuleCommand;
+pub use server::ServerCommand;
+pub use staff::StaffCommand;
+pub use stats::StatsCommand;
+pub use status::StatusCommand;
 pub use summon::SummonCommand;
 pub use user_id::UserIdCommand;
+pub use watchdog::WatchdogCommand;
+pub use whitelist::WhitelistCommand;
 
+use crate::services::SS14DatabaseService;
+use log::warn;
 use serenity::all::{
-    Color, CreateCommand, CreateEmbed, CreateEmbedFooter, CreateInteractionResponse,
-    CreateInteractionResponseFollowup, CreateInteractionResponseMessage, Permissions,
-    ResolvedOption, ResolvedValue,
+    AutocompleteChoice, AutocompleteOption, Color, CommandInteraction, ComponentInteraction,
+    Context, CreateAutocompleteResponse, CreateCommand, CreateEmbed, CreateEmbedFooter,
+    CreateInteractionResponse, CreateInteractionResponseFollowup, CreateInteractionResponseMessage,
+    ModalInteraction, Permissions, ResolvedOption, ResolvedValue,
 };
 use serenity::async_trait;
 
 const MANAGE_WEBHOOKS_SERVER_PERMISSION: Permissions =
     Permissions::from_bits(0x20 | 0x20000000).unwrap();
+/// Discord allows at most 25 autocomplete choices.
+const MAX_LOGIN_SUGGESTIONS: usize = 25;
+const MIN_LOGIN_QUERY_LENGTH: usize = 2;
 
 #[derive(Debug)]
 pub enum DiscordCommandResponse {
@@ -94,7 +138,43 @@ impl DiscordCommandResponse {
 pub trait DiscordCommandHandler: Send + Sync + std::fmt::Debug {
     fn definition(&self) -> DiscordCommandDefinition;
     fn registration(&self) -> CreateCommand;
-    async fn handler(&self, opts: &[ResolvedOption]) -> DiscordCommandResponse;
+    async fn handler(
+        &self,
+        ctx: &Context,
+        cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse;
+
+    /// Provides choices for the focused autocomplete option, if command has any.
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        _focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        None
+    }
+
+    /// Handles a component interaction whose custom ID starts with `<command name>:`.
+    /// Returns `None` if the interaction is not handled or was already responded to,
+    /// e.g. deferred before slow work.
+    async fn component(
+        &self,
+        _ctx: &Context,
+        _component: &ComponentInteraction,
+    ) -> Option<CreateInteractionResponse> {
+        None
+    }
+
+    /// Handles a modal submit whose custom ID starts with `<command name>:`.
+    /// Returns `None` the same way as [`DiscordCommandHandler::component`].
+    async fn modal(
+        &self,
+        _ctx: &Context,
+        _modal: &ModalInteraction,
+    ) -> Option<CreateInteractionResponse> {
+        None
+    }
 }
 
 /// Represents some settings for discord commands.
@@ -105,6 +185,8 @@ pub struct DiscordCommandDefinition {
     pub is_global: bool,
     pub is_deferred: bool,
     pub is_ephemeral: bool,
+    /// In-game admin flags the invoker must have, checked through their linked SS14 account.
+    pub admin_flags: &'static [&'static str],
 }
 
 impl DiscordCommandDefinition {
@@ -114,6 +196,7 @@ impl DiscordCommandDefinition {
             is_deferred,
             is_global,
             is_ephemeral,
+            admin_flags: &[],
         }
     }
 
@@ -123,6 +206,7 @@ impl DiscordCommandDefinition {
             is_global: true,
             is_deferred,
             is_ephemeral,
+            admin_flags: &[],
         }
     }
 
@@ -132,8 +216,15 @@ impl DiscordCommandDefinition {
             is_global: false,
             is_deferred,
             is_ephemeral,
+            admin_flags: &[],
         }
     }
+
+    /// Requires the invoker to have all of `flags` in game, e.g. `BAN` or `PERMISSIONS`.
+    pub fn with_admin_flags(mut self, flags: &'static [&'static str]) -> Self {
+        self.admin_flags = flags;
+        self
+    }
 }
 
 fn opts_get_login(opts: &[ResolvedOption]) -> Option<String> {
@@ -148,3 +239,51 @@ fn opts_get_login(opts: &[ResolvedOption]) -> Option<String> {
 
     login
 }
+
+/// Suggests in-game logins for a focused `login` option. Prefix matches go first,
+/// the rest is filled with trigram matches when the database supports them.
+async fn login_autocomplete(
+    ss14_db: &SS14DatabaseService,
+    focused: &AutocompleteOption<'_>,
+) -> Option<CreateAutocompleteResponse> {
+    if focused.name != "login" {
+        return None;
+    }
+
+    let query = focused.value.trim();
+    if query.chars().count() < MIN_LOGIN_QUERY_LENGTH {
+        return Some(CreateAutocompleteResponse::new());
+    }
+
+    let limit = MAX_LOGIN_SUGGESTIONS as i64;
+    let mut logins = match ss14_db.search_logins_by_prefix(query, limit).await {
+        Ok(logins) => logins,
+        Err(e) => {
+            warn!("Failed to search logins by prefix: {}", e);
+            vec![]
+        }
+    };
+
+    if logins.len() < MAX_LOGIN_SUGGESTIONS {
+        match ss14_db.search_logins_by_similarity(query, limit).await {
+            Ok(similar) => {
+                for login in similar {
+                    if logins.len() >= MAX_LOGIN_SUGGESTIONS {
+                        break;
+                    }
+                    if !logins.contains(&login) {
+                        logins.push(login);
+                    }
+                }
+            }
+            Err(e) => warn!("Failed to search logins by similarity: {}", e),
+        }
+    }
+
+    let choices = logins
+        .into_iter()
+        .map(|login| AutocompleteChoice::new(login.clone(), login))
+        .collect();
+
+    Some(CreateAutocompleteResponse::new().set_choices(choices))
+}
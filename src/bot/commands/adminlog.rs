@@ -0,0 +1,195 @@
+use super::*;
+use crate::services::{
+    AdminLogFilter, SS14AuthClientService, SS14DatabaseService, ServicesContainer,
+};
+use crate::utils::{gen_random_color, resolve_user_id, truncate_chars};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateAttachment, CreateCommandOption};
+use serenity::async_trait;
+
+const PAGE_SIZE: i64 = 20;
+/// Discord limits embed descriptions to 4096 characters, leave some room for the header.
+const MAX_LIST_LENGTH: usize = 3800;
+const MAX_MESSAGE_LENGTH: usize = 180;
+/// Upper bound of exported entries, keeps the attachment under Discord's upload limit.
+const EXPORT_LIMIT: i64 = 20000;
+
+#[derive(Debug)]
+pub struct AdminLogCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl AdminLogCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for AdminLogCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("adminlog", true, true).with_admin_flags(&["LOGS"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("adminlog")
+            .name_localized("ru", "админлог")
+            .description("Searches admin logs of a round")
+            .description_localized("ru", "Ищет по админ-логам раунда")
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "round", "Round ID")
+                    .name_localized("ru", "раунд")
+                    .description_localized("ru", "Номер раунда")
+                    .min_int_value(1)
+                    .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "type", "Log type ID")
+                    .name_localized("ru", "тип")
+                    .description_localized("ru", "Номер типа лога")
+                    .min_int_value(0),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "player",
+                    "In-game login or UUID of an involved player",
+                )
+                .name_localized("ru", "игрок")
+                .description_localized("ru", "Логин или UUID причастного игрока"),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::String, "text", "Text to search for")
+                    .name_localized("ru", "текст")
+                    .description_localized("ru", "Искомый текст"),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "page", "Page number")
+                    .name_localized("ru", "страница")
+                    .description_localized("ru", "Номер страницы")
+                    .min_int_value(1),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Boolean,
+                    "export",
+                    "Attaches all matches as a text file",
+                )
+                .name_localized("ru", "экспорт")
+                .description_localized("ru", "Прикрепляет все совпадения текстовым файлом"),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let round_id = try_discord_unwrap!(
+            extract_discord_arg!(opts, "round", Integer),
+            none => "Round ID is not specified",
+            ephemeral => true
+        );
+
+        let player = match extract_discord_arg!(opts, "player", String) {
+            Some(login) => Some(try_discord_unwrap!(
+                resolve_user_id(&login, &self.ss14_client).await,
+                none => "Such player doesn't exist.",
+                error => "❌ An error occurred while fetching UUID.",
+                log => "Failed to get UID by login.",
+                ephemeral => true
+            )),
+            None => None,
+        };
+
+        let text = extract_discord_arg!(opts, "text", String);
+        let filter = AdminLogFilter {
+            round_id: *round_id as i32,
+            log_type: extract_discord_arg!(opts, "type", Integer).map(|t| *t as i32),
+            player,
+            text: text.as_deref(),
+        };
+        let page = extract_discord_arg!(opts, "page", Integer)
+            .copied()
+            .unwrap_or(1);
+
+        let (entries, total) = try_discord_unwrap!(
+            self.ss14_db
+                .search_admin_logs(&filter, (page - 1) * PAGE_SIZE, PAGE_SIZE)
+                .await,
+            error => "❌ An error occurred while searching admin logs.",
+            log => "Failed to search admin logs.",
+            ephemeral => true
+        );
+
+        let pages = ((total + PAGE_SIZE - 1) / PAGE_SIZE).max(1);
+        let mut content = format!(
+            "📜 **Admin logs of round #{}**, {} match(es), page {}/{}\n",
+            filter.round_id, total, page, pages
+        );
+
+        if entries.is_empty() {
+            content.push_str("No matches found.");
+        }
+
+        for entry in &entries {
+            let line = format!(
+                "`#{}` <t:{}:T> `[{}]` {}\n",
+                entry.id,
+                entry.date.timestamp(),
+                entry.log_type,
+                truncate_chars(&entry.message, MAX_MESSAGE_LENGTH)
+            );
+
+            if content.len() + line.len() > MAX_LIST_LENGTH {
+                break;
+            }
+            content.push_str(&line);
+        }
+
+        let mut response = CreateInteractionResponseFollowup::new()
+            .embed(
+                CreateEmbed::new()
+                    .description(content)
+                    .footer(CreateEmbedFooter::new("[type] is the numeric log type"))
+                    .color(gen_random_color()),
+            )
+            .ephemeral(true);
+
+        if extract_discord_arg!(opts, "export", Boolean).is_some_and(|e| *e) && total > 0 {
+            let (entries, _) = try_discord_unwrap!(
+                self.ss14_db.search_admin_logs(&filter, 0, EXPORT_LIMIT).await,
+                error => "❌ An error occurred while exporting admin logs.",
+                log => "Failed to export admin logs.",
+                ephemeral => true
+            );
+
+            let export: String = entries
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "{} #{} type={} impact={} {}\n",
+                        entry.date.to_rfc3339(),
+                        entry.id,
+                        entry.log_type,
+                        entry.impact,
+                        entry.message
+                    )
+                })
+                .collect();
+
+            response = response.add_file(CreateAttachment::bytes(
+                export.into_bytes(),
+                format!("round-{}-adminlog.txt", filter.round_id),
+            ));
+        }
+
+        DiscordCommandResponse::Followup(response)
+    }
+}
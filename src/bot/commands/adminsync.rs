@@ -0,0 +1,153 @@
+use super::*;
+use crate::bot::tasks::admin_sync::{apply_admin_sync, plan_admin_sync, AdminRankChange};
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::try_discord_unwrap;
+use crate::utils::gen_random_color;
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+/// Discord limits embed descriptions to 4096 characters, leave some room for the truncation note.
+const MAX_LIST_LENGTH: usize = 3800;
+
+#[derive(Debug)]
+pub struct AdminSyncCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl AdminSyncCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    async fn push_changes(&self, content: &mut String, changes: &[&AdminRankChange]) {
+        for (shown, change) in changes.iter().enumerate() {
+            let login: Option<String> = self
+                .ss14_db
+                .get_login(change.user_id)
+                .await
+                .unwrap_or_default();
+            let line = format!(
+                "`{}` ({}): {} → {}\n",
+                login.as_deref().unwrap_or("unknown"),
+                change.user_id,
+                change.from.as_deref().unwrap_or("*not admin*"),
+                change.to.as_deref().unwrap_or("*removed*")
+            );
+
+            if content.len() + line.len() > MAX_LIST_LENGTH {
+                content.push_str(&format!("...and {} more\n", changes.len() - shown));
+                break;
+            }
+            content.push_str(&line);
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for AdminSyncCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("adminsync", true, true)
+            .with_admin_flags(&["PERMISSIONS"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("adminsync")
+            .name_localized("ru", "синхронизация_админов")
+            .description("Syncs Discord roles to in-game admin ranks")
+            .description_localized("ru", "Синхронизирует роли Discord с игровыми админ-рангами")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "preview",
+                    "Shows changes without applying them",
+                )
+                .name_localized("ru", "предпросмотр")
+                .description_localized("ru", "Показывает изменения, не применяя их"),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "apply", "Applies changes")
+                    .name_localized("ru", "применить")
+                    .description_localized("ru", "Применяет изменения"),
+            )
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+    }
+
+    async fn handler(
+        &self,
+        ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let sub =
+            try_discord_unwrap!(opts.first(), none => "No command supplied", ephemeral => true);
+
+        let apply = match sub.name {
+            "preview" => false,
+            "apply" => true,
+            _ => return DiscordCommandResponse::followup_response("No command supplied", true),
+        };
+
+        let changes = try_discord_unwrap!(
+            plan_admin_sync(&ctx.http, &self.ss14_client, &self.ss14_db).await,
+            error => "❌ An error occurred while comparing admin ranks.",
+            log => "Failed to plan admin sync.",
+            ephemeral => true
+        );
+
+        if changes.is_empty() {
+            return DiscordCommandResponse::followup_embed_response(
+                "🛡️ Admin ranks are in sync.",
+                None,
+                Some(gen_random_color()),
+                true,
+            );
+        }
+
+        if !apply {
+            let mut content = "🛡️ **Pending admin rank changes**\n".to_string();
+            self.push_changes(&mut content, &changes.iter().collect::<Vec<_>>())
+                .await;
+
+            return DiscordCommandResponse::followup_embed_response(
+                &content,
+                None,
+                Some(gen_random_color()),
+                true,
+            );
+        }
+
+        let failed = try_discord_unwrap!(
+            apply_admin_sync(&self.ss14_db, &changes).await,
+            error => "❌ An error occurred while applying admin rank changes.",
+            log => "Failed to apply admin sync.",
+            ephemeral => true
+        );
+        // a player has at most one change, so the user ID identifies it
+        let applied: Vec<_> = changes
+            .iter()
+            .filter(|c| !failed.iter().any(|f| f.user_id == c.user_id))
+            .collect();
+
+        let mut content = "🛡️ **Applied admin rank changes**\n".to_string();
+        if applied.is_empty() {
+            content.push_str("None.\n");
+        }
+        self.push_changes(&mut content, &applied).await;
+
+        if !failed.is_empty() {
+            content.push_str("\n⚠️ **Failed, these ranks don't exist in game**\n");
+            self.push_changes(&mut content, &failed).await;
+        }
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+}
@@ -0,0 +1,374 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::{config_channel, gen_random_uuid, RED_COLOR};
+use crate::{config_get_array, extract_discord_arg};
+use log::error;
+use serenity::all::{
+    ActionRowComponent, ButtonStyle, ChannelId, CommandOptionType, CreateActionRow, CreateButton,
+    CreateCommandOption, CreateInputText, CreateMessage, CreateModal, EditInteractionResponse,
+    InputTextStyle, UserId,
+};
+use serenity::async_trait;
+
+/// Discord allows at most 5 text inputs per modal.
+const MAX_QUESTIONS: usize = 5;
+const MAX_ANSWER_LENGTH: u16 = 1000;
+const MAX_LABEL_LENGTH: usize = 45;
+const APPROVED_COLOR: Color = Color::from_rgb(0, 200, 0);
+const PENDING_COLOR: Color = Color::from_rgb(255, 255, 0);
+const DEFAULT_QUESTION: &str = "Why do you want to apply?";
+/// In-game flags `/whitelist` requires.
+const REVIEWER_FLAGS: &[&str] = &["BAN"];
+
+#[derive(Debug)]
+pub struct ApplyCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl ApplyCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    /// Posts the application for review. Returns `false` if the applicant is not linked.
+    async fn submit(
+        &self,
+        ctx: &Context,
+        modal: &ModalInteraction,
+        job: Option<&str>,
+    ) -> Result<bool, crate::error::Error> {
+        let Some(user_id) = self
+            .ss14_client
+            .get_user_id_from_discord(modal.user.id.to_string())
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        let review_channel = review_channel()
+            .ok_or_else(|| crate::error::Error::bot("Review channel is not configured"))?;
+
+        let questions = configured_questions();
+        let answers = modal
+            .data
+            .components
+            .iter()
+            .flat_map(|row| row.components.iter())
+            .filter_map(|component| match component {
+                ActionRowComponent::InputText(input) => {
+                    Some(input.value.clone().unwrap_or_default())
+                }
+                _ => None,
+            });
+
+        let login = self.ss14_db.get_login(user_id).await?;
+
+        let mut embed = CreateEmbed::new()
+            .title(application_title(job))
+            .description(format!(
+                "**Applicant:** <@{}>\n**In-Game Login:** `{}`",
+                modal.user.id,
+                login.as_deref().unwrap_or("unknown")
+            ))
+            .color(PENDING_COLOR);
+
+        for (question, answer) in questions.iter().zip(answers) {
+            embed = embed.field(*question, answer, false);
+        }
+
+        let suffix = match job {
+            Some(job) => format!("{}:{}", modal.user.id, job),
+            None => modal.user.id.to_string(),
+        };
+        let buttons = CreateActionRow::Buttons(vec![
+            CreateButton::new(format!("apply:approve:{}", suffix))
+                .label("Approve")
+                .style(ButtonStyle::Success),
+            CreateButton::new(format!("apply:reject:{}", suffix))
+                .label("Reject")
+                .style(ButtonStyle::Danger),
+        ]);
+
+        review_channel
+            .send_message(
+                &ctx.http,
+                CreateMessage::new().embed(embed).components(vec![buttons]),
+            )
+            .await?;
+
+        Ok(true)
+    }
+
+    /// Reviewers need the same in-game flags as `/whitelist`, since approving writes the same rows.
+    async fn is_reviewer(&self, reviewer: UserId) -> Result<bool, crate::error::Error> {
+        let Some(user_id) = self
+            .ss14_client
+            .get_user_id_from_discord(reviewer.to_string())
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        self.ss14_db.has_admin_flags(user_id, REVIEWER_FLAGS).await
+    }
+
+    /// Writes the whitelist row for the applicant. Returns `false` if the applicant is not linked.
+    async fn approve(
+        &self,
+        applicant: UserId,
+        job: Option<&str>,
+    ) -> Result<bool, crate::error::Error> {
+        let Some(user_id) = self
+            .ss14_client
+            .get_user_id_from_discord(applicant.to_string())
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        match job {
+            Some(job) => self.ss14_db.add_job_whitelist(user_id, job).await?,
+            None => self.ss14_db.add_to_whitelist(user_id).await?,
+        };
+
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for ApplyCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("apply", false, true)
+    }
+
+    fn registration(&self) -> CreateCommand {
+        let mut job_option =
+            CreateCommandOption::new(CommandOptionType::String, "job", "Job to apply for")
+                .name_localized("ru", "профессия")
+                .description_localized("ru", "Профессия, на которую подаётся заявка");
+        for job in config_get_array!("applications.jobs", as_array, as_str).unwrap_or_default() {
+            job_option = job_option.add_string_choice(job, job);
+        }
+
+        CreateCommand::new("apply")
+            .name_localized("ru", "заявка")
+            .description("Applies for the whitelist or a job whitelist")
+            .description_localized("ru", "Подаёт заявку в вайтлист или на профессию")
+            .add_option(job_option)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        if review_channel().is_none() {
+            return DiscordCommandResponse::default_embed_response(
+                "Applications are not configured.",
+                None,
+                Some(RED_COLOR),
+                true,
+            );
+        }
+
+        // the link is checked on submit, a modal can't wait for the link service
+        let job = extract_discord_arg!(opts, "job", String);
+        let custom_id = match &job {
+            Some(job) => format!("apply:submit:{}", job),
+            None => "apply:submit".to_string(),
+        };
+
+        let inputs = configured_questions()
+            .into_iter()
+            .enumerate()
+            .map(|(i, question)| {
+                let label: String = question.chars().take(MAX_LABEL_LENGTH).collect();
+                CreateActionRow::InputText(
+                    CreateInputText::new(InputTextStyle::Paragraph, label, format!("q{}", i))
+                        .max_length(MAX_ANSWER_LENGTH),
+                )
+            })
+            .collect();
+
+        DiscordCommandResponse::Default(CreateInteractionResponse::Modal(
+            CreateModal::new(custom_id, application_title(job.as_deref())).components(inputs),
+        ))
+    }
+
+    async fn modal(
+        &self,
+        ctx: &Context,
+        modal: &ModalInteraction,
+    ) -> Option<CreateInteractionResponse> {
+        let mut parts = modal.data.custom_id.split(':').skip(1);
+        if parts.next() != Some("submit") {
+            return None;
+        }
+        let job = parts.next();
+
+        // submitting needs the link service and a discord post, acknowledge first
+        if let Err(e) = modal.defer_ephemeral(&ctx.http).await {
+            error!("Failed to defer application submit: {}", e);
+            return None;
+        }
+
+        let content = match self.submit(ctx, modal, job).await {
+            Ok(true) => "✅ Your application has been submitted.".to_string(),
+            Ok(false) => "🔍 Link your SS14 account before applying.".to_string(),
+            Err(e) => {
+                let err_id = gen_random_uuid();
+                error!("{}. Failed to submit application. Error: {}", err_id, e);
+                format!(
+                    "❌ An error occurred while submitting application.\nError ID: {}",
+                    err_id
+                )
+            }
+        };
+
+        if let Err(e) = modal
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+            .await
+        {
+            error!("Failed to respond to application submit: {}", e);
+        }
+
+        None
+    }
+
+    async fn component(
+        &self,
+        ctx: &Context,
+        component: &ComponentInteraction,
+    ) -> Option<CreateInteractionResponse> {
+        let mut parts = component.data.custom_id.split(':').skip(1);
+        let action = parts.next()?;
+        let applicant = UserId::new(parts.next()?.parse().ok()?);
+        let job = parts.next();
+        if !matches!(action, "approve" | "reject") {
+            return None;
+        }
+
+        // reviewing needs the link service, the database and a DM, acknowledge first
+        if let Err(e) = component.defer(&ctx.http).await {
+            error!("Failed to defer application review: {}", e);
+            return None;
+        }
+
+        let ephemeral = |content: String| async move {
+            let followup = CreateInteractionResponseFollowup::new()
+                .content(content)
+                .ephemeral(true);
+            if let Err(e) = component.create_followup(&ctx.http, followup).await {
+                error!("Failed to respond to application review: {}", e);
+            }
+        };
+
+        match self.is_reviewer(component.user.id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                ephemeral(format!(
+                    "⛔ Reviewing applications requires the {} flag in game.",
+                    REVIEWER_FLAGS.join(", ")
+                ))
+                .await;
+                return None;
+            }
+            Err(e) => {
+                let err_id = gen_random_uuid();
+                error!("{}. Failed to check reviewer flags. Error: {}", err_id, e);
+                ephemeral(format!(
+                    "❌ An error occurred while checking your flags.\nError ID: {}",
+                    err_id
+                ))
+                .await;
+                return None;
+            }
+        }
+
+        let approved = action == "approve";
+        if approved {
+            match self.approve(applicant, job).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    ephemeral("🔍 Applicant is no longer linked.".to_string()).await;
+                    return None;
+                }
+                Err(e) => {
+                    let err_id = gen_random_uuid();
+                    error!("{}. Failed to approve application. Error: {}", err_id, e);
+                    ephemeral(format!(
+                        "❌ An error occurred while approving application.\nError ID: {}",
+                        err_id
+                    ))
+                    .await;
+                    return None;
+                }
+            }
+        }
+
+        let verdict = if approved { "Approved" } else { "Rejected" };
+        let mut embed = component
+            .message
+            .embeds
+            .first()
+            .cloned()
+            .map(CreateEmbed::from)
+            .unwrap_or_default()
+            .field(verdict, format!("<@{}>", component.user.id), false);
+        embed = embed.color(if approved { APPROVED_COLOR } else { RED_COLOR });
+
+        if let Err(e) = component
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .embed(embed)
+                    .components(vec![]),
+            )
+            .await
+        {
+            error!("Failed to update application {}: {}", applicant, e);
+        }
+
+        let notification = format!(
+            "{} Your {} has been {}.",
+            if approved { "✅" } else { "❌" },
+            application_title(job).to_lowercase(),
+            verdict.to_lowercase()
+        );
+        if let Err(e) = applicant
+            .direct_message(&ctx.http, CreateMessage::new().content(notification))
+            .await
+        {
+            error!("Failed to notify applicant {}: {}", applicant, e);
+        }
+
+        None
+    }
+}
+
+fn review_channel() -> Option<ChannelId> {
+    config_channel("applications.review_channel")
+}
+
+/// Discord rejects modals without inputs, so an empty config falls back to [`DEFAULT_QUESTION`].
+fn configured_questions() -> Vec<&'static str> {
+    let mut questions =
+        config_get_array!("applications.questions", as_array, as_str).unwrap_or_default();
+    questions.truncate(MAX_QUESTIONS);
+    if questions.is_empty() {
+        questions.push(DEFAULT_QUESTION);
+    }
+    questions
+}
+
+fn application_title(job: Option<&str>) -> String {
+    match job {
+        Some(job) => format!("{} application", job),
+        None => "Whitelist application".to_string(),
+    }
+}
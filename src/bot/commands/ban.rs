@@ -0,0 +1,214 @@
+use super::*;
+use crate::services::{
+    PardonResult, SS14AuthClientService, SS14DatabaseService, ServicesContainer,
+};
+use crate::utils::{gen_random_color, parse_ban_expiration};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+#[derive(Debug)]
+pub struct BanCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl BanCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for BanCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("ban", true, true).with_admin_flags(&["BAN"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("ban")
+            .name_localized("ru", "бан")
+            .description("Issues and lifts server bans")
+            .description_localized("ru", "Выдаёт и снимает серверные баны")
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "add", "Bans a player")
+                    .name_localized("ru", "выдать")
+                    .description_localized("ru", "Банит игрока")
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::String,
+                            "login",
+                            "In-game login",
+                        )
+                        .set_autocomplete(true)
+                        .name_localized("ru", "логин")
+                        .description_localized("ru", "Внутриигровой логин")
+                        .required(true),
+                    )
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::String,
+                            "duration",
+                            "Ban duration, e.g. 30m, 12h, 1w2d. 0 or perm for permanent",
+                        )
+                        .name_localized("ru", "срок")
+                        .description_localized(
+                            "ru",
+                            "Срок бана, например 30m, 12h, 1w2d. 0 или perm для перманентного",
+                        )
+                        .required(true),
+                    )
+                    .add_sub_option(
+                        CreateCommandOption::new(CommandOptionType::String, "reason", "Ban reason")
+                            .name_localized("ru", "причина")
+                            .description_localized("ru", "Причина бана")
+                            .required(true),
+                    ),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "pardon", "Lifts a ban")
+                    .name_localized("ru", "снять")
+                    .description_localized("ru", "Снимает бан")
+                    .add_sub_option(
+                        CreateCommandOption::new(CommandOptionType::Integer, "id", "Ban ID")
+                            .description_localized("ru", "ID бана")
+                            .required(true),
+                    ),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let command = try_discord_unwrap!(
+            map_command(opts),
+            none => "No command supplied",
+            ephemeral => true
+        );
+
+        let admin = try_discord_unwrap!(
+            self.ss14_client.get_user_id_from_discord(cmd.user.id.to_string()).await,
+            none => "🔍 Your Discord account is not linked to an SS14 account.",
+            error => "❌ An error occurred while fetching your UUID.",
+            log => "Failed to get admin UID by Discord ID.",
+            ephemeral => true
+        );
+
+        match command {
+            BanSubCommand::Add {
+                login,
+                duration,
+                reason,
+            } => {
+                let expiration_time = try_discord_unwrap!(
+                    parse_ban_expiration(&duration),
+                    none => "Invalid duration. Use formats like 30m, 12h or 1w2d.",
+                    ephemeral => true
+                );
+
+                let player = try_discord_unwrap!(
+                    self.ss14_client.get_user_id(login.clone()).await,
+                    none => "Such player doesn't exist.",
+                    error => "❌ An error occurred while fetching UUID.",
+                    log => "Failed to get UID by login.",
+                    ephemeral => true
+                );
+
+                let ban_id = try_discord_unwrap!(
+                    self.ss14_db
+                        .add_server_ban(admin, player, &reason, expiration_time)
+                        .await,
+                    none => "⛔ You don't have the BAN flag in game.",
+                    error => "❌ An error occurred while inserting the ban.",
+                    log => "Failed to insert server ban.",
+                    ephemeral => true
+                );
+
+                let expires = match expiration_time {
+                    Some(time) => format!("<t:{}:f>", time.timestamp()),
+                    None => "Never".to_string(),
+                };
+
+                DiscordCommandResponse::followup_embed_response(
+                    &format!(
+                        "🔨 **Banned:** `{}`\n**Ban ID:** {}\n**Expires:** {}\n**Reason:** {}",
+                        login, ban_id, expires, reason
+                    ),
+                    None,
+                    Some(gen_random_color()),
+                    true,
+                )
+            }
+            BanSubCommand::Pardon(ban_id) => {
+                let result = try_discord_unwrap!(
+                    self.ss14_db.pardon_server_ban(admin, ban_id).await,
+                    error => "❌ An error occurred while lifting the ban.",
+                    log => "Failed to insert server unban.",
+                    ephemeral => true
+                );
+
+                let message = match result {
+                    PardonResult::Pardoned => format!("✅ Ban {} has been lifted.", ban_id),
+                    PardonResult::NotFound => format!("🔍 Ban {} doesn't exist.", ban_id),
+                    PardonResult::AlreadyPardoned => {
+                        format!("Ban {} has already been lifted.", ban_id)
+                    }
+                    PardonResult::MissingFlag => "⛔ You don't have the BAN flag in game.".into(),
+                };
+
+                DiscordCommandResponse::followup_embed_response(
+                    &message,
+                    None,
+                    Some(gen_random_color()),
+                    true,
+                )
+            }
+        }
+    }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
+}
+
+enum BanSubCommand {
+    Add {
+        login: String,
+        duration: String,
+        reason: String,
+    },
+    Pardon(i32),
+}
+
+fn map_command(opts: &[ResolvedOption]) -> Option<BanSubCommand> {
+    let sub = opts.first()?;
+    let sub_opts = match &sub.value {
+        ResolvedValue::SubCommand(opts) => opts,
+        _ => return None,
+    };
+
+    match sub.name {
+        "add" => Some(BanSubCommand::Add {
+            login: extract_discord_arg!(sub_opts, "login", String)?,
+            duration: extract_discord_arg!(sub_opts, "duration", String)?,
+            reason: extract_discord_arg!(sub_opts, "reason", String)?,
+        }),
+        "pardon" => {
+            let id = extract_discord_arg!(sub_opts, "id", Integer)?;
+            Some(BanSubCommand::Pardon(i32::try_from(*id).ok()?))
+        }
+        _ => None,
+    }
+}
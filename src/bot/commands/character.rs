@@ -0,0 +1,116 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::{gen_random_color, truncate_chars};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use log::error;
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+const MAX_RESULTS: i64 = 10;
+const MAX_NAME_LENGTH: usize = 100;
+
+#[derive(Debug)]
+pub struct CharacterCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl CharacterCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for CharacterCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("character", true, true).with_admin_flags(&["ADMIN"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("character")
+            .name_localized("ru", "персонаж")
+            .description("Finds the owner of a character by name")
+            .description_localized("ru", "Ищет владельца персонажа по имени")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "name",
+                    "Character name or its part",
+                )
+                .name_localized("ru", "имя")
+                .description_localized("ru", "Имя персонажа или его часть")
+                .min_length(2)
+                .required(true),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let name = try_discord_unwrap!(
+            extract_discord_arg!(opts, "name", String),
+            none => "Name is not specified",
+            ephemeral => true
+        );
+
+        let characters = try_discord_unwrap!(
+            self.ss14_db.find_characters(&name, MAX_RESULTS + 1).await,
+            error => "❌ An error occurred while searching characters.",
+            log => "Failed to search characters.",
+            ephemeral => true
+        );
+
+        if characters.is_empty() {
+            return DiscordCommandResponse::followup_embed_response(
+                &format!("No characters matching `{}` found.", name),
+                None,
+                Some(gen_random_color()),
+                true,
+            );
+        }
+
+        let mut content = format!(
+            "🧑‍🚀 **Characters matching** `{}`\n",
+            truncate_chars(&name, MAX_NAME_LENGTH)
+        );
+        for character in characters.iter().take(MAX_RESULTS as usize) {
+            let discord = match self.ss14_client.get_discord_id(character.user_id).await {
+                Ok(Some(id)) => format!("<@{}>", id),
+                Ok(None) => "not linked".to_string(),
+                Err(e) => {
+                    error!("Failed to get Discord ID of {}: {}", character.user_id, e);
+                    "unknown".to_string()
+                }
+            };
+
+            content.push_str(&format!(
+                "**{}** ({}, {}), slot {}\n`{}` ({}), {}\n",
+                character.name,
+                character.species,
+                character.age,
+                character.slot,
+                character.login.as_deref().unwrap_or("unknown"),
+                character.user_id,
+                discord
+            ));
+        }
+
+        let footer = (characters.len() as i64 > MAX_RESULTS)
+            .then_some("More characters match, refine the name to narrow results");
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            footer,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+}
@@ -0,0 +1,116 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::{gen_random_color, resolve_user_id};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+#[derive(Debug)]
+pub struct CharactersCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl CharactersCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for CharactersCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("characters", true, true).with_admin_flags(&["ADMIN"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("characters")
+            .name_localized("ru", "персонажи")
+            .description("Lists character slots of a player")
+            .description_localized("ru", "Показывает слоты персонажей игрока")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "login",
+                    "In-game login or UUID",
+                )
+                .set_autocomplete(true)
+                .name_localized("ru", "логин")
+                .description_localized("ru", "Внутриигровой логин или UUID")
+                .required(true),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let login = try_discord_unwrap!(
+            extract_discord_arg!(opts, "login", String),
+            none => "Login is not specified",
+            ephemeral => true
+        );
+
+        let player = try_discord_unwrap!(
+            resolve_user_id(&login, &self.ss14_client).await,
+            none => "Such player doesn't exist.",
+            error => "❌ An error occurred while fetching UUID.",
+            log => "Failed to get UID by login.",
+            ephemeral => true
+        );
+
+        let characters = try_discord_unwrap!(
+            self.ss14_db.get_characters(player).await,
+            error => "❌ An error occurred while fetching characters.",
+            log => "Failed to fetch characters.",
+            ephemeral => true
+        );
+
+        let discord = try_discord_unwrap!(
+            self.ss14_client.get_discord_id(player).await,
+            error => "❌ An error occurred while fetching Discord ID.",
+            log => "Failed to get Discord ID by UID.",
+            ephemeral => true
+        )
+        .map(|id| format!("<@{}>", id))
+        .unwrap_or_else(|| "not linked".to_string());
+
+        let mut content = format!(
+            "🧑‍🚀 **Characters of** `{}` ({}), {}\n",
+            login, player, discord
+        );
+
+        if characters.is_empty() {
+            content.push_str("No characters found.");
+        }
+
+        for character in &characters {
+            content.push_str(&format!(
+                "**{}.** {} ({}, {})\n",
+                character.slot, character.name, character.species, character.age
+            ));
+        }
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
+}
@@ -24,7 +24,12 @@ impl DiscordCommandHandler for FemboyCommand {
             .description(">w<")
     }
 
-    async fn handler(&self, _opts: &[ResolvedOption]) -> DiscordCommandResponse {
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        _opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
         let mut rng = rand::rng();
         let random_index = rng.random_range(0..FEMBOY_IMAGES.len());
         let random_img = FEMBOY_IMAGES[random_index];
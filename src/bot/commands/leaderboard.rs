@@ -0,0 +1,135 @@
+use super::*;
+use crate::services::{SS14DatabaseService, ServicesContainer};
+use crate::utils::{configured_trackers, format_playtime, gen_random_color, tracker_display_name};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+const OVERALL_TRACKER: &str = "Overall";
+const LEADERBOARD_SIZE: i64 = 15;
+/// Discord allows at most 25 choices per option.
+const MAX_CHOICES: usize = 25;
+
+#[derive(Debug)]
+pub struct LeaderboardCommand {
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl LeaderboardCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for LeaderboardCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("leaderboard", true, false)
+    }
+
+    fn registration(&self) -> CreateCommand {
+        let mut role_option = CreateCommandOption::new(
+            CommandOptionType::String,
+            "role",
+            "Role tracker, overall by default",
+        )
+        .name_localized("ru", "роль")
+        .description_localized("ru", "Роль, по умолчанию общее время");
+        for (tracker, name) in configured_trackers().into_iter().take(MAX_CHOICES) {
+            role_option = role_option.add_string_choice(name, tracker);
+        }
+
+        CreateCommand::new("leaderboard")
+            .name_localized("ru", "топ")
+            .description("Shows player leaderboards")
+            .description_localized("ru", "Показывает рейтинги игроков")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "playtime",
+                    "Players with the most playtime",
+                )
+                .name_localized("ru", "время")
+                .description_localized("ru", "Игроки с наибольшим наигранным временем")
+                .add_sub_option(role_option)
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "period",
+                        "Only players active during the period, ranked by total playtime",
+                    )
+                    .name_localized("ru", "период")
+                    .description_localized(
+                        "ru",
+                        "Только игроки, заходившие за период, по общему времени",
+                    )
+                    .add_string_choice("Day", "day")
+                    .add_string_choice("Week", "week")
+                    .add_string_choice("Month", "month")
+                    .add_string_choice("All time", "all"),
+                ),
+            )
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let sub =
+            try_discord_unwrap!(opts.first(), none => "No command supplied", ephemeral => false);
+        let sub_opts = match (sub.name, &sub.value) {
+            ("playtime", ResolvedValue::SubCommand(opts)) => opts,
+            _ => return DiscordCommandResponse::followup_response("No command supplied", false),
+        };
+
+        let tracker = extract_discord_arg!(sub_opts, "role", String)
+            .unwrap_or_else(|| OVERALL_TRACKER.to_string());
+        let period = extract_discord_arg!(sub_opts, "period", String);
+        let (seen_since, period_name) = match period.as_deref() {
+            Some("day") => (Some(chrono::Duration::days(1)), "active in the last day"),
+            Some("week") => (Some(chrono::Duration::weeks(1)), "active in the last week"),
+            Some("month") => (Some(chrono::Duration::days(30)), "active in the last month"),
+            _ => (None, "all players"),
+        };
+        let seen_since = seen_since.map(|d| chrono::Utc::now() - d);
+
+        let leaderboard = try_discord_unwrap!(
+            self.ss14_db
+                .get_playtime_leaderboard(&tracker, seen_since, LEADERBOARD_SIZE)
+                .await,
+            error => "❌ An error occurred while fetching leaderboard.",
+            log => "Failed to fetch playtime leaderboard.",
+            ephemeral => false
+        );
+
+        let mut content = format!(
+            "🏆 **{} total playtime leaderboard, {}**\n",
+            tracker_display_name(&tracker),
+            period_name
+        );
+
+        if leaderboard.is_empty() {
+            content.push_str("No players found.");
+        }
+
+        for (place, (_, login, seconds)) in leaderboard.iter().enumerate() {
+            content.push_str(&format!(
+                "**{}.** `{}`: {}\n",
+                place + 1,
+                login,
+                format_playtime(*seconds)
+            ));
+        }
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            None,
+            Some(gen_random_color()),
+            false,
+        )
+    }
+}
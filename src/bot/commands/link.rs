@@ -3,8 +3,9 @@
 use log::error;
 use serenity::{
     all::{
-        CommandOptionType, CreateCommand, CreateCommandOption, ResolvedOption, ResolvedValue, User,
-        UserId,
+        AutocompleteOption, CommandInteraction, CommandOptionType, Context,
+        CreateAutocompleteResponse, CreateCommand, CreateCommandOption, ResolvedOption,
+        ResolvedValue, User, UserId,
     },
     async_trait,
 };
@@ -16,7 +17,10 @@ use crate::{
     utils::{format_extra_data, gen_random_color, gen_random_uuid, RED_COLOR},
 };
 
-use super::{DiscordCommandDefinition, DiscordCommandHandler, DiscordCommandResponse};
+use super::{
+    login_autocomplete, DiscordCommandDefinition, DiscordCommandHandler, DiscordCommandResponse,
+    MANAGE_WEBHOOKS_SERVER_PERMISSION,
+};
 
 #[derive(Debug)]
 pub struct LinkCommand {
@@ -36,13 +40,14 @@ impl LinkCommand {
 #[async_trait]
 impl DiscordCommandHandler for LinkCommand {
     fn definition(&self) -> DiscordCommandDefinition {
-        DiscordCommandDefinition::new_global("link", true, true)
+        DiscordCommandDefinition::new_global("link", true, true).with_admin_flags(&["ADMIN"])
     }
 
     fn registration(&self) -> CreateCommand {
         CreateCommand::new("link")
             .name_localized("ru", "привязка")
             .description("Operates with discord and SS14 links")
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
             .add_option(
                 CreateCommandOption::new(
                     CommandOptionType::SubCommandGroup,
@@ -105,6 +110,7 @@ impl DiscordCommandHandler for LinkCommand {
                             "login",
                             "In-Game Login",
                         )
+                        .set_autocomplete(true)
                         .name_localized("ru", "логин")
                         .description_localized("ru", "Внутриигровой логин")
                         .required(true),
@@ -124,6 +130,7 @@ impl DiscordCommandHandler for LinkCommand {
                             "login",
                             "Login to unlink",
                         )
+                        .set_autocomplete(true)
                         .name_localized("ru", "логин")
                         .description_localized("ru", "Логин пользователя, которого отвязать"),
                     ),
@@ -131,7 +138,12 @@ impl DiscordCommandHandler for LinkCommand {
             )
     }
 
-    async fn handler(&self, opts: &[ResolvedOption]) -> DiscordCommandResponse {
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
         let command = map_command(opts);
 
         let command =
@@ -280,6 +292,15 @@ impl DiscordCommandHandler for LinkCommand {
             },
         }
     }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
 }
 
 enum LinkSubCommand {
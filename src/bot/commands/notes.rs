@@ -0,0 +1,330 @@
+use super::*;
+use crate::services::{
+    NewAdminRemark, NoteSeverity, RemarkKind, SS14AuthClientService, SS14DatabaseService,
+    ServicesContainer,
+};
+use crate::utils::{gen_random_color, parse_ban_expiration, resolve_user_id};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+use uuid::Uuid;
+
+/// Discord limits embed descriptions to 4096 characters, leave some room for the truncation note.
+const MAX_LIST_LENGTH: usize = 3800;
+
+#[derive(Debug)]
+pub struct NotesCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl NotesCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    async fn handle_list(
+        &self,
+        login: &str,
+        player: Uuid,
+        severity: Option<NoteSeverity>,
+    ) -> DiscordCommandResponse {
+        let remarks = try_discord_unwrap!(
+            self.ss14_db.get_admin_remarks(player).await,
+            error => "❌ An error occurred while fetching notes.",
+            log => "Failed to fetch admin remarks.",
+            ephemeral => true
+        );
+
+        let remarks: Vec<_> = remarks
+            .into_iter()
+            .filter(|r| severity.is_none() || r.severity == severity)
+            .collect();
+
+        if remarks.is_empty() {
+            return DiscordCommandResponse::followup_embed_response(
+                &format!("`{}` has no notes.", login),
+                None,
+                Some(gen_random_color()),
+                true,
+            );
+        }
+
+        let mut content = format!("🗂️ **Notes of** `{}`\n", login);
+        for (shown, remark) in remarks.iter().enumerate() {
+            let icon = match remark.kind {
+                RemarkKind::Note => "📝",
+                RemarkKind::Message => "✉️",
+                RemarkKind::Watchlist => "👁️",
+            };
+            let severity = remark
+                .severity
+                .map(|s| format!(" **[{}]**", s.name()))
+                .unwrap_or_default();
+            let secret = if remark.secret { " 🔒" } else { "" };
+
+            let line = format!(
+                "{} `#{}`{}{} by `{}` <t:{}:d>: {}\n",
+                icon,
+                remark.id,
+                severity,
+                secret,
+                remark.created_by.as_deref().unwrap_or("unknown"),
+                remark.created_at.timestamp(),
+                remark.message
+            );
+
+            if content.len() + line.len() > MAX_LIST_LENGTH {
+                content.push_str(&format!("...and {} more", remarks.len() - shown));
+                break;
+            }
+            content.push_str(&line);
+        }
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+
+    async fn handle_add(
+        &self,
+        cmd: &CommandInteraction,
+        login: &str,
+        remark: NewAdminRemark<'_>,
+    ) -> DiscordCommandResponse {
+        let admin = try_discord_unwrap!(
+            self.ss14_client.get_user_id_from_discord(cmd.user.id.to_string()).await,
+            none => "🔍 Your Discord account is not linked to an SS14 account.",
+            error => "❌ An error occurred while fetching your UUID.",
+            log => "Failed to get admin UID by Discord ID.",
+            ephemeral => true
+        );
+
+        let kind = remark.kind;
+        let id = try_discord_unwrap!(
+            self.ss14_db.add_admin_remark(admin, remark).await,
+            none => "⛔ You don't have the EDITNOTES flag in game.",
+            error => "❌ An error occurred while inserting the note.",
+            log => "Failed to insert admin remark.",
+            ephemeral => true
+        );
+
+        let kind = match kind {
+            RemarkKind::Note => "Note",
+            RemarkKind::Message => "Message",
+            RemarkKind::Watchlist => "Watchlist entry",
+        };
+
+        DiscordCommandResponse::followup_embed_response(
+            &format!("✅ {} `#{}` has been added to `{}`.", kind, id, login),
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for NotesCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("notes", true, true).with_admin_flags(&["VIEWNOTES"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        let login_option = || {
+            CreateCommandOption::new(CommandOptionType::String, "login", "In-game login or UUID")
+                .set_autocomplete(true)
+                .name_localized("ru", "логин")
+                .description_localized("ru", "Внутриигровой логин или UUID")
+                .required(true)
+        };
+        let text_option = || {
+            CreateCommandOption::new(CommandOptionType::String, "text", "Text")
+                .name_localized("ru", "текст")
+                .description_localized("ru", "Текст")
+                .required(true)
+        };
+        let severity_option = |description: &str, description_ru: &str| {
+            CreateCommandOption::new(CommandOptionType::Integer, "severity", description)
+                .name_localized("ru", "серьёзность")
+                .description_localized("ru", description_ru)
+                .add_int_choice(NoteSeverity::None.name(), NoteSeverity::None as i32)
+                .add_int_choice(NoteSeverity::Minor.name(), NoteSeverity::Minor as i32)
+                .add_int_choice(NoteSeverity::Medium.name(), NoteSeverity::Medium as i32)
+                .add_int_choice(NoteSeverity::High.name(), NoteSeverity::High as i32)
+        };
+
+        CreateCommand::new("notes")
+            .name_localized("ru", "заметки")
+            .description("Manages admin notes, messages and watchlists")
+            .description_localized("ru", "Управляет заметками, сообщениями и вотчлистами")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "list",
+                    "Lists notes, messages and watchlists of a player",
+                )
+                .name_localized("ru", "список")
+                .description_localized("ru", "Показывает заметки, сообщения и вотчлисты игрока")
+                .add_sub_option(login_option())
+                .add_sub_option(severity_option(
+                    "Shows only notes of given severity",
+                    "Показывает только заметки данной серьёзности",
+                )),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "add", "Adds a note")
+                    .name_localized("ru", "добавить")
+                    .description_localized("ru", "Добавляет заметку")
+                    .add_sub_option(login_option())
+                    .add_sub_option(text_option())
+                    .add_sub_option(severity_option(
+                        "Note severity, Minor by default",
+                        "Серьёзность заметки, по умолчанию Minor",
+                    ))
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::Boolean,
+                            "secret",
+                            "Hides the note from the player",
+                        )
+                        .name_localized("ru", "секретная")
+                        .description_localized("ru", "Скрывает заметку от игрока"),
+                    ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "message",
+                    "Sends an admin message shown to the player on join",
+                )
+                .name_localized("ru", "сообщение")
+                .description_localized("ru", "Отправляет админ-сообщение, видимое игроку при входе")
+                .add_sub_option(login_option())
+                .add_sub_option(text_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "watchlist",
+                    "Puts a player on the watchlist",
+                )
+                .name_localized("ru", "вотчлист")
+                .description_localized("ru", "Добавляет игрока в вотчлист")
+                .add_sub_option(login_option())
+                .add_sub_option(text_option())
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "duration",
+                        "Duration, e.g. 12h or 1w2d. Permanent by default",
+                    )
+                    .name_localized("ru", "срок")
+                    .description_localized(
+                        "ru",
+                        "Срок, например 12h или 1w2d. По умолчанию бессрочно",
+                    ),
+                ),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let sub =
+            try_discord_unwrap!(opts.first(), none => "No command supplied", ephemeral => true);
+        let sub_opts = match &sub.value {
+            ResolvedValue::SubCommand(opts) => opts,
+            _ => return DiscordCommandResponse::followup_response("No command supplied", true),
+        };
+
+        let login = try_discord_unwrap!(
+            extract_discord_arg!(sub_opts, "login", String),
+            none => "Login is not specified",
+            ephemeral => true
+        );
+
+        let player = try_discord_unwrap!(
+            resolve_user_id(&login, &self.ss14_client).await,
+            none => "Such player doesn't exist.",
+            error => "❌ An error occurred while fetching UUID.",
+            log => "Failed to get UID by login.",
+            ephemeral => true
+        );
+
+        let severity = extract_discord_arg!(sub_opts, "severity", Integer)
+            .and_then(|s| NoteSeverity::from_i32(*s as i32));
+
+        if sub.name == "list" {
+            return self.handle_list(&login, player, severity).await;
+        }
+
+        let text = try_discord_unwrap!(
+            extract_discord_arg!(sub_opts, "text", String),
+            none => "Text is not specified",
+            ephemeral => true
+        );
+
+        let remark = match sub.name {
+            "add" => NewAdminRemark {
+                kind: RemarkKind::Note,
+                player,
+                message: &text,
+                severity: severity.unwrap_or(NoteSeverity::Minor),
+                secret: extract_discord_arg!(sub_opts, "secret", Boolean)
+                    .copied()
+                    .unwrap_or(false),
+                expiration_time: None,
+            },
+            "message" => NewAdminRemark {
+                kind: RemarkKind::Message,
+                player,
+                message: &text,
+                severity: NoteSeverity::None,
+                secret: false,
+                expiration_time: None,
+            },
+            "watchlist" => {
+                let expiration_time = match extract_discord_arg!(sub_opts, "duration", String) {
+                    Some(duration) => try_discord_unwrap!(
+                        parse_ban_expiration(&duration),
+                        none => "Invalid duration. Use formats like 30m, 12h or 1w2d.",
+                        ephemeral => true
+                    ),
+                    None => None,
+                };
+
+                NewAdminRemark {
+                    kind: RemarkKind::Watchlist,
+                    player,
+                    message: &text,
+                    severity: NoteSeverity::None,
+                    secret: false,
+                    expiration_time,
+                }
+            }
+            _ => return DiscordCommandResponse::followup_response("No command supplied", true),
+        };
+
+        self.handle_add(cmd, &login, remark).await
+    }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
+}
@@ -17,7 +17,12 @@ impl DiscordCommandHandler for PingCommand {
             .description_localized("ru", "Пингует бота")
     }
 
-    async fn handler(&self, _opts: &[ResolvedOption]) -> DiscordCommandResponse {
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        _opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
         DiscordCommandResponse::default_response("Pong!", false)
     }
 }
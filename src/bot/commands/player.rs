@@ -0,0 +1,176 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::{
+    alts_lookback_days, gen_random_color, hash_identifier, mask_address, resolve_user_id,
+};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+/// Discord limits embed descriptions to 4096 characters, leave some room for the truncation note.
+const MAX_LIST_LENGTH: usize = 3800;
+
+#[derive(Debug)]
+pub struct PlayerCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl PlayerCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    async fn handle_alts(&self, login: String, days: Option<i64>) -> DiscordCommandResponse {
+        let player = try_discord_unwrap!(
+            resolve_user_id(&login, &self.ss14_client).await,
+            none => "Such player doesn't exist.",
+            error => "❌ An error occurred while fetching UUID.",
+            log => "Failed to get UID by login.",
+            ephemeral => true
+        );
+
+        let days = days.unwrap_or_else(alts_lookback_days);
+        let since = chrono::Utc::now() - chrono::Duration::days(days);
+
+        let alts = try_discord_unwrap!(
+            self.ss14_db.find_alts(player, since).await,
+            error => "❌ An error occurred while searching connection log.",
+            log => "Failed to find alts.",
+            ephemeral => true
+        );
+
+        let target_banned = try_discord_unwrap!(
+            self.ss14_db.get_banned_among(&[player]).await,
+            error => "❌ An error occurred while fetching bans.",
+            log => "Failed to fetch bans.",
+            ephemeral => true
+        );
+
+        let mut content = format!(
+            "🕵️ **Accounts sharing identifiers with** `{}`{} **in the last {} days**\n",
+            login,
+            if target_banned.is_empty() { "" } else { " ⛔" },
+            days
+        );
+
+        if alts.is_empty() {
+            content.push_str("No matches found.");
+        }
+
+        for (shown, alt) in alts.iter().enumerate() {
+            let mut identifiers: Vec<String> = alt
+                .shared_addresses
+                .iter()
+                .map(|a| format!("IP `{}`", mask_address(a)))
+                .collect();
+            identifiers.extend(
+                alt.shared_hwids
+                    .iter()
+                    .map(|h| format!("HWID `{}`", hash_identifier(h))),
+            );
+
+            let line = format!(
+                "{}`{}` ({}): {}\n",
+                if alt.banned { "⛔ " } else { "" },
+                alt.login.as_deref().unwrap_or("unknown"),
+                alt.user_id,
+                identifiers.join(", ")
+            );
+
+            if content.len() + line.len() > MAX_LIST_LENGTH {
+                content.push_str(&format!("...and {} more", alts.len() - shown));
+                break;
+            }
+            content.push_str(&line);
+        }
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            Some("⛔ marks accounts with an active ban"),
+            Some(gen_random_color()),
+            true,
+        )
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for PlayerCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("player", true, true).with_admin_flags(&["PII"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("player")
+            .name_localized("ru", "игрок")
+            .description("Player investigation tools")
+            .description_localized("ru", "Инструменты для проверки игроков")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "alts",
+                    "Finds accounts sharing IP or HWID with a player",
+                )
+                .name_localized("ru", "мульты")
+                .description_localized("ru", "Ищет аккаунты с общими IP или HWID")
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "login",
+                        "In-game login or UUID",
+                    )
+                    .set_autocomplete(true)
+                    .name_localized("ru", "логин")
+                    .description_localized("ru", "Внутриигровой логин или UUID")
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Integer,
+                        "days",
+                        "How many days of connection log to search",
+                    )
+                    .name_localized("ru", "дни")
+                    .description_localized("ru", "За сколько дней искать подключения")
+                    .min_int_value(1)
+                    .max_int_value(3650),
+                ),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let sub =
+            try_discord_unwrap!(opts.first(), none => "No command supplied", ephemeral => true);
+        let sub_opts = match (sub.name, &sub.value) {
+            ("alts", ResolvedValue::SubCommand(opts)) => opts,
+            _ => return DiscordCommandResponse::followup_response("No command supplied", true),
+        };
+
+        let login = try_discord_unwrap!(
+            extract_discord_arg!(sub_opts, "login", String),
+            none => "Login is not specified",
+            ephemeral => true
+        );
+        let days = extract_discord_arg!(sub_opts, "days", Integer).copied();
+
+        self.handle_alts(login, days).await
+    }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
+}
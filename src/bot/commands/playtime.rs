@@ -0,0 +1,117 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::{format_playtime, gen_random_color, resolve_user_id, tracker_display_name};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+/// Discord limits embed descriptions to 4096 characters, leave some room for the truncation note.
+const MAX_LIST_LENGTH: usize = 3800;
+
+#[derive(Debug)]
+pub struct PlaytimeCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl PlaytimeCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for PlaytimeCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("playtime", true, false)
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("playtime")
+            .name_localized("ru", "время")
+            .description("Shows playtime of a player")
+            .description_localized("ru", "Показывает наигранное время игрока")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "login",
+                    "In-game login or UUID",
+                )
+                .set_autocomplete(true)
+                .name_localized("ru", "логин")
+                .description_localized("ru", "Внутриигровой логин или UUID")
+                .required(true),
+            )
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let login = try_discord_unwrap!(
+            extract_discord_arg!(opts, "login", String),
+            none => "Login is not specified",
+            ephemeral => false
+        );
+
+        let player = try_discord_unwrap!(
+            resolve_user_id(&login, &self.ss14_client).await,
+            none => "Such player doesn't exist.",
+            error => "❌ An error occurred while fetching UUID.",
+            log => "Failed to get UID by login.",
+            ephemeral => false
+        );
+
+        let playtime = try_discord_unwrap!(
+            self.ss14_db.get_playtime(player).await,
+            error => "❌ An error occurred while fetching playtime.",
+            log => "Failed to fetch playtime.",
+            ephemeral => false
+        );
+
+        if playtime.is_empty() {
+            return DiscordCommandResponse::followup_embed_response(
+                &format!("`{}` has no recorded playtime.", login),
+                None,
+                Some(gen_random_color()),
+                false,
+            );
+        }
+
+        let mut content = format!("⏱️ **Playtime of** `{}`\n", login);
+        for (shown, (tracker, seconds)) in playtime.iter().enumerate() {
+            let line = format!(
+                "**{}:** {}\n",
+                tracker_display_name(tracker),
+                format_playtime(*seconds)
+            );
+
+            if content.len() + line.len() > MAX_LIST_LENGTH {
+                content.push_str(&format!("...and {} more", playtime.len() - shown));
+                break;
+            }
+            content.push_str(&line);
+        }
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            None,
+            Some(gen_random_color()),
+            false,
+        )
+    }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
+}
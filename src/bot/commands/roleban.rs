@@ -0,0 +1,444 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::{
+    configured_departments, gen_random_color, parse_ban_expiration, truncate_chars,
+};
+use crate::{config_get_array, extract_discord_arg, try_discord_unwrap};
+use serenity::all::{AutocompleteChoice, CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+use uuid::Uuid;
+
+/// Prefix SS14 uses for job role bans in `server_role_ban.role_id`.
+const JOB_ROLE_PREFIX: &str = "Job:";
+const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+/// Discord limits embed descriptions to 4096 characters.
+const MAX_MESSAGE_LENGTH: usize = 3800;
+
+#[derive(Debug)]
+pub struct RoleBanCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl RoleBanCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    async fn handle_add(
+        &self,
+        cmd: &CommandInteraction,
+        login: String,
+        player: Uuid,
+        role: String,
+        duration: String,
+        reason: String,
+    ) -> DiscordCommandResponse {
+        let admin = try_discord_unwrap!(
+            self.ss14_client.get_user_id_from_discord(cmd.user.id.to_string()).await,
+            none => "🔍 Your Discord account is not linked to an SS14 account.",
+            error => "❌ An error occurred while fetching your UUID.",
+            log => "Failed to get admin UID by Discord ID.",
+            ephemeral => true
+        );
+
+        let expiration_time = try_discord_unwrap!(
+            parse_ban_expiration(&duration),
+            none => "Invalid duration. Use formats like 30m, 12h or 1w2d.",
+            ephemeral => true
+        );
+
+        let role_ids = try_discord_unwrap!(
+            resolve_role_ids(&role),
+            none => &unknown_role_message(&role),
+            ephemeral => true
+        );
+        let ids = try_discord_unwrap!(
+            self.ss14_db
+                .add_role_bans(admin, player, &role_ids, &reason, expiration_time)
+                .await,
+            none => "⛔ You don't have the BAN flag in game.",
+            error => "❌ An error occurred while inserting role bans.",
+            log => "Failed to insert role bans.",
+            ephemeral => true
+        );
+
+        let expires = match expiration_time {
+            Some(time) => format!("<t:{}:f>", time.timestamp()),
+            None => "Never".to_string(),
+        };
+        let ids = ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        DiscordCommandResponse::followup_embed_response(
+            &format!(
+                "🚫 **Role banned:** `{}`\n**Roles:** {}\n**Ban IDs:** {}\n**Expires:** {}\n**Reason:** {}",
+                login,
+                format_role_ids(&role_ids),
+                ids,
+                expires,
+                reason
+            ),
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+
+    async fn handle_remove(
+        &self,
+        cmd: &CommandInteraction,
+        login: String,
+        player: Uuid,
+        role: String,
+    ) -> DiscordCommandResponse {
+        let admin = try_discord_unwrap!(
+            self.ss14_client.get_user_id_from_discord(cmd.user.id.to_string()).await,
+            none => "🔍 Your Discord account is not linked to an SS14 account.",
+            error => "❌ An error occurred while fetching your UUID.",
+            log => "Failed to get admin UID by Discord ID.",
+            ephemeral => true
+        );
+
+        // bans of jobs that are no longer configured can still be lifted
+        let role_ids =
+            resolve_role_ids(&role).unwrap_or_else(|| vec![format!("{}{}", JOB_ROLE_PREFIX, role)]);
+        let lifted = try_discord_unwrap!(
+            self.ss14_db.remove_role_bans(admin, player, &role_ids).await,
+            none => "⛔ You don't have the BAN flag in game.",
+            error => "❌ An error occurred while lifting role bans.",
+            log => "Failed to insert role unbans.",
+            ephemeral => true
+        );
+
+        let message = if lifted == 0 {
+            format!(
+                "🔍 `{}` has no active bans for {}.",
+                login,
+                format_role_ids(&role_ids)
+            )
+        } else {
+            format!(
+                "✅ Lifted {} role ban(s) of `{}` for {}.",
+                lifted,
+                login,
+                format_role_ids(&role_ids)
+            )
+        };
+
+        DiscordCommandResponse::followup_embed_response(
+            &message,
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+
+    async fn handle_list(&self, login: String, player: Uuid) -> DiscordCommandResponse {
+        let bans = try_discord_unwrap!(
+            self.ss14_db.get_role_bans(player).await,
+            error => "❌ An error occurred while fetching role bans.",
+            log => "Failed to fetch role bans.",
+            ephemeral => true
+        );
+
+        if bans.is_empty() {
+            return DiscordCommandResponse::followup_embed_response(
+                &format!("`{}` has no active role bans.", login),
+                None,
+                Some(gen_random_color()),
+                true,
+            );
+        }
+
+        let mut content = format!("🚫 **Active role bans of** `{}`\n", login);
+        for (shown, ban) in bans.iter().enumerate() {
+            let expires = match ban.expiration_time {
+                Some(time) => format!("<t:{}:R>", time.timestamp()),
+                None => "never".to_string(),
+            };
+            let line = format!(
+                "`#{}` **{}**, expires {}: {}\n",
+                ban.id,
+                ban.role_id.trim_start_matches(JOB_ROLE_PREFIX),
+                expires,
+                ban.reason
+            );
+
+            if content.len() + line.len() > MAX_MESSAGE_LENGTH {
+                content.push_str(&format!("...and {} more", bans.len() - shown));
+                break;
+            }
+            content.push_str(&line);
+        }
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for RoleBanCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("roleban", true, true).with_admin_flags(&["BAN"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        let login_option = || {
+            CreateCommandOption::new(CommandOptionType::String, "login", "In-game login")
+                .set_autocomplete(true)
+                .name_localized("ru", "логин")
+                .description_localized("ru", "Внутриигровой логин")
+                .required(true)
+        };
+        let role_option = || {
+            CreateCommandOption::new(CommandOptionType::String, "role", "Job or department")
+                .name_localized("ru", "роль")
+                .description_localized("ru", "Профессия или отдел")
+                .set_autocomplete(true)
+                .required(true)
+        };
+
+        CreateCommand::new("roleban")
+            .name_localized("ru", "джоббан")
+            .description("Manages role (job) bans")
+            .description_localized("ru", "Управляет банами ролей")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "add",
+                    "Bans a player from a job or a whole department",
+                )
+                .name_localized("ru", "выдать")
+                .description_localized("ru", "Банит игрока на профессию или весь отдел")
+                .add_sub_option(login_option())
+                .add_sub_option(role_option())
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "duration",
+                        "Ban duration, e.g. 30m, 12h, 1w2d. 0 or perm for permanent",
+                    )
+                    .name_localized("ru", "срок")
+                    .description_localized(
+                        "ru",
+                        "Срок бана, например 30m, 12h, 1w2d. 0 или perm для перманентного",
+                    )
+                    .required(true),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::String, "reason", "Ban reason")
+                        .name_localized("ru", "причина")
+                        .description_localized("ru", "Причина бана")
+                        .required(true),
+                ),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "remove",
+                    "Lifts active role bans from a job or a whole department",
+                )
+                .name_localized("ru", "снять")
+                .description_localized("ru", "Снимает активные баны с профессии или отдела")
+                .add_sub_option(login_option())
+                .add_sub_option(role_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "list",
+                    "Lists active role bans of a player",
+                )
+                .name_localized("ru", "список")
+                .description_localized("ru", "Показывает активные баны ролей игрока")
+                .add_sub_option(login_option()),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let command = try_discord_unwrap!(
+            map_command(opts),
+            none => "No command supplied",
+            ephemeral => true
+        );
+
+        let login = match &command {
+            RoleBanSubCommand::Add { login, .. }
+            | RoleBanSubCommand::Remove { login, .. }
+            | RoleBanSubCommand::List { login } => login.clone(),
+        };
+
+        let player = try_discord_unwrap!(
+            self.ss14_client.get_user_id(login.clone()).await,
+            none => "Such player doesn't exist.",
+            error => "❌ An error occurred while fetching UUID.",
+            log => "Failed to get UID by login.",
+            ephemeral => true
+        );
+
+        match command {
+            RoleBanSubCommand::Add {
+                role,
+                duration,
+                reason,
+                ..
+            } => {
+                self.handle_add(cmd, login, player, role, duration, reason)
+                    .await
+            }
+            RoleBanSubCommand::Remove { role, .. } => {
+                self.handle_remove(cmd, login, player, role).await
+            }
+            RoleBanSubCommand::List { .. } => self.handle_list(login, player).await,
+        }
+    }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        if focused.name == "login" {
+            return login_autocomplete(&self.ss14_db, &focused).await;
+        }
+        if focused.name != "role" {
+            return None;
+        }
+
+        let query = focused.value.to_lowercase();
+        let departments = configured_departments()
+            .into_iter()
+            .map(|(name, _)| (format!("{} (department)", name), name));
+        let jobs = configured_jobs().into_iter().map(|job| (job.clone(), job));
+
+        let choices = departments
+            .chain(jobs)
+            .filter(|(_, value)| value.to_lowercase().contains(&query))
+            .take(MAX_AUTOCOMPLETE_CHOICES)
+            .map(|(name, value)| AutocompleteChoice::new(name, value))
+            .collect();
+
+        Some(CreateAutocompleteResponse::new().set_choices(choices))
+    }
+}
+
+enum RoleBanSubCommand {
+    Add {
+        login: String,
+        role: String,
+        duration: String,
+        reason: String,
+    },
+    Remove {
+        login: String,
+        role: String,
+    },
+    List {
+        login: String,
+    },
+}
+
+fn map_command(opts: &[ResolvedOption]) -> Option<RoleBanSubCommand> {
+    let sub = opts.first()?;
+    let sub_opts = match &sub.value {
+        ResolvedValue::SubCommand(opts) => opts,
+        _ => return None,
+    };
+
+    let login = extract_discord_arg!(sub_opts, "login", String)?;
+
+    match sub.name {
+        "add" => Some(RoleBanSubCommand::Add {
+            login,
+            role: extract_discord_arg!(sub_opts, "role", String)?,
+            duration: extract_discord_arg!(sub_opts, "duration", String)?,
+            reason: extract_discord_arg!(sub_opts, "reason", String)?,
+        }),
+        "remove" => Some(RoleBanSubCommand::Remove {
+            login,
+            role: extract_discord_arg!(sub_opts, "role", String)?,
+        }),
+        "list" => Some(RoleBanSubCommand::List { login }),
+        _ => None,
+    }
+}
+
+/// Every known job prototype ID, from `roles.jobs` and department groupings.
+fn configured_jobs() -> Vec<String> {
+    let mut jobs: Vec<String> = config_get_array!("roles.jobs", as_array, as_str)
+        .unwrap_or_default()
+        .into_iter()
+        .map(str::to_string)
+        .chain(
+            configured_departments()
+                .into_iter()
+                .flat_map(|(_, jobs)| jobs),
+        )
+        .collect();
+
+    jobs.sort();
+    jobs.dedup();
+    jobs
+}
+
+/// Expands a department into its jobs and prefixes them the way SS14 stores role bans.
+/// Returns `None` if `role` is neither a configured department nor a configured job.
+fn resolve_role_ids(role: &str) -> Option<Vec<String>> {
+    let jobs = configured_departments()
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(role))
+        .map(|(_, jobs)| jobs)
+        .or_else(|| {
+            configured_jobs()
+                .into_iter()
+                .find(|job| job.eq_ignore_ascii_case(role))
+                .map(|job| vec![job])
+        })?;
+
+    Some(
+        jobs.into_iter()
+            .map(|job| format!("{}{}", JOB_ROLE_PREFIX, job))
+            .collect(),
+    )
+}
+
+fn unknown_role_message(role: &str) -> String {
+    let valid = configured_departments()
+        .into_iter()
+        .map(|(name, _)| name)
+        .chain(configured_jobs())
+        .map(|role| format!("`{}`", role))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    truncate_chars(
+        &format!("Unknown role `{}`. Valid roles: {}", role, valid),
+        MAX_MESSAGE_LENGTH,
+    )
+}
+
+fn format_role_ids(role_ids: &[String]) -> String {
+    role_ids
+        .iter()
+        .map(|id| format!("`{}`", id.trim_start_matches(JOB_ROLE_PREFIX)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,80 @@
+use super::*;
+use crate::services::{SS14DatabaseService, ServicesContainer};
+use crate::utils::gen_random_color;
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+#[derive(Debug)]
+pub struct RoundCommand {
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl RoundCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for RoundCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("round", true, true).with_admin_flags(&["LOGS"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("round")
+            .name_localized("ru", "раунд")
+            .description("Shows information about a round")
+            .description_localized("ru", "Показывает информацию о раунде")
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "id", "Round ID")
+                    .description_localized("ru", "Номер раунда")
+                    .min_int_value(1)
+                    .required(true),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let round_id = try_discord_unwrap!(
+            extract_discord_arg!(opts, "id", Integer),
+            none => "Round ID is not specified",
+            ephemeral => true
+        );
+
+        let round = try_discord_unwrap!(
+            self.ss14_db.get_round(*round_id as i32).await,
+            none => "Such round doesn't exist.",
+            error => "❌ An error occurred while fetching the round.",
+            log => "Failed to fetch round.",
+            ephemeral => true
+        );
+
+        let timestamp = |date: Option<chrono::DateTime<chrono::Utc>>| {
+            date.map(|d| format!("<t:{}:f>", d.timestamp()))
+                .unwrap_or_else(|| "unknown".to_string())
+        };
+
+        DiscordCommandResponse::followup_embed_response(
+            &format!(
+                "🎲 **Round #{}**\n**Server:** {}\n**Started:** {}\n**Last log entry:** {}\n**Players:** {}",
+                round.id,
+                round.server_name.as_deref().unwrap_or("unknown"),
+                timestamp(round.start_date),
+                timestamp(round.last_log_date),
+                round.player_count
+            ),
+            Some("SS14 does not store round end time or map, last log entry is shown instead"),
+            Some(gen_random_color()),
+            true,
+        )
+    }
+}
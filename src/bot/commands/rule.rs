@@ -0,0 +1,463 @@
+use super::*;
+use crate::utils::{gen_random_color, truncate_chars};
+use crate::{config_get, extract_discord_arg, try_discord_unwrap};
+use log::error;
+use serenity::all::{CommandOptionType, CreateAllowedMentions, CreateCommandOption};
+use serenity::async_trait;
+
+/// Discord limits embed descriptions to 4096 characters.
+const MAX_RULE_LENGTH: usize = 3800;
+/// Discord limits embed titles to 256 characters.
+const MAX_TITLE_LENGTH: usize = 256;
+/// Discord allows at most 25 autocomplete choices of at most 100 characters.
+const MAX_RULE_SUGGESTIONS: usize = 25;
+const MAX_CHOICE_LENGTH: usize = 100;
+/// XML and rich text tags of the SS14 guidebook, other bracketed text like `[IC]` is kept.
+const MARKUP_TAGS: &[&str] = &[
+    "Document",
+    "Box",
+    "ColorBox",
+    "Table",
+    "GuideEntityEmbed",
+    "GuideReagentEmbed",
+    "GuideReagentGroupEmbed",
+    "GuideMicrowaveEmbed",
+    "color",
+    "bold",
+    "italic",
+    "bolditalic",
+    "head",
+    "bullet",
+    "textlink",
+    "cmdlink",
+    "font",
+    "mono",
+    "keybind",
+    "protodata",
+    "scramble",
+];
+
+#[derive(Debug)]
+struct Rule {
+    /// Number of the rule as written in the file, e.g. `1`, `2.3` or `C1`.
+    id: Option<String>,
+    title: String,
+    text: String,
+}
+
+impl Rule {
+    fn heading(&self) -> String {
+        match &self.id {
+            Some(id) => format!("{}. {}", id, self.title),
+            None => self.title.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RuleCommand;
+
+#[async_trait]
+impl DiscordCommandHandler for RuleCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("rule", false, false)
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("rule")
+            .name_localized("ru", "правило")
+            .description("Shows a server rule")
+            .description_localized("ru", "Показывает правило сервера")
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "rule",
+                    "Rule number or keyword",
+                )
+                .set_autocomplete(true)
+                .name_localized("ru", "правило")
+                .description_localized("ru", "Номер правила или ключевое слово")
+                .required(true),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::User,
+                    "member",
+                    "Member the rule is cited to",
+                )
+                .name_localized("ru", "участник")
+                .description_localized("ru", "Участник, которому цитируется правило")
+                .required(false),
+            )
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let query = try_discord_unwrap!(
+            extract_discord_arg!(opts, "rule", String),
+            none => "Rule is not specified",
+            ephemeral => true
+        );
+
+        let member = opts.iter().find_map(|opt| match (opt.name, &opt.value) {
+            ("member", ResolvedValue::User(user, _)) => Some(user.id),
+            _ => None,
+        });
+
+        let rules = try_discord_unwrap!(
+            load_rules().await,
+            error => "❌ An error occurred while loading rules.",
+            log => "Failed to load rules.",
+            ephemeral => true
+        );
+
+        let rule = try_discord_unwrap!(
+            find_rule(&rules, &query),
+            none => "Such rule doesn't exist.",
+            ephemeral => true
+        );
+
+        let embed = CreateEmbed::new()
+            .title(truncate_chars(&rule.heading(), MAX_TITLE_LENGTH))
+            .description(truncate_chars(&rule.text, MAX_RULE_LENGTH))
+            .color(gen_random_color());
+
+        let mut message = CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .allowed_mentions(CreateAllowedMentions::new());
+
+        if let Some(member) = member {
+            message = message
+                .content(format!("<@{}>", member))
+                .allowed_mentions(CreateAllowedMentions::new().users([member]));
+        }
+
+        DiscordCommandResponse::Default(CreateInteractionResponse::Message(message))
+    }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        if focused.name != "rule" {
+            return None;
+        }
+
+        let rules = match load_rules().await {
+            Ok(rules) => rules,
+            Err(e) => {
+                error!("Failed to load rules: {}", e);
+                return Some(CreateAutocompleteResponse::new());
+            }
+        };
+
+        let query = focused.value.trim().to_lowercase();
+        let choices = rules
+            .iter()
+            .filter(|rule| {
+                query.is_empty()
+                    || rule
+                        .id
+                        .as_ref()
+                        .is_some_and(|id| id.to_lowercase().starts_with(&query))
+                    || rule.title.to_lowercase().contains(&query)
+            })
+            .take(MAX_RULE_SUGGESTIONS)
+            .map(|rule| {
+                let value = rule.id.clone().unwrap_or_else(|| rule.title.clone());
+                AutocompleteChoice::new(
+                    truncate_chars(&rule.heading(), MAX_CHOICE_LENGTH),
+                    truncate_chars(&value, MAX_CHOICE_LENGTH),
+                )
+            })
+            .collect();
+
+        Some(CreateAutocompleteResponse::new().set_choices(choices))
+    }
+}
+
+/// Reads rules from `rules.path` on every use, so edits don't need a restart.
+async fn load_rules() -> Result<Vec<Rule>, crate::error::Error> {
+    let path = config_get!("rules.path", as_str)
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| crate::error::Error::bot("Rules path is not configured"))?;
+
+    Ok(parse_rules(&tokio::fs::read_to_string(path).await?))
+}
+
+/// Exact rule number first, then a keyword in the title, then a keyword in the text.
+fn find_rule<'a>(rules: &'a [Rule], query: &str) -> Option<&'a Rule> {
+    let query = query.trim().trim_end_matches('.').to_lowercase();
+
+    rules
+        .iter()
+        .find(|r| r.id.as_ref().is_some_and(|id| id.to_lowercase() == query))
+        .or_else(|| {
+            rules
+                .iter()
+                .find(|r| r.title.to_lowercase().contains(&query))
+        })
+        .or_else(|| {
+            rules
+                .iter()
+                .find(|r| r.text.to_lowercase().contains(&query))
+        })
+}
+
+/// Splits rules by headings and top level numbered list items. Accepts Markdown and SS14
+/// guidebook documents, which are Markdown wrapped in `<Document>` with `[color]`/`[bold]` markup.
+fn parse_rules(source: &str) -> Vec<Rule> {
+    let mut rules: Vec<Rule> = vec![];
+
+    for line in source.lines() {
+        let line = strip_markup(line);
+        let trimmed = line.trim();
+
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            if heading.is_empty() {
+                continue;
+            }
+
+            let (id, title) = split_rule_id(heading);
+            rules.push(Rule {
+                id,
+                title,
+                text: String::new(),
+            });
+            continue;
+        }
+
+        // top level numbered list items are rules too, e.g. `1. Don't grief`
+        if !line.starts_with(char::is_whitespace) {
+            if let Some((id, title)) = split_list_marker(trimmed) {
+                rules.push(Rule {
+                    id: Some(id),
+                    text: format!("{}\n", title),
+                    title,
+                });
+                continue;
+            }
+        }
+
+        if let Some(rule) = rules.last_mut() {
+            if !rule.text.is_empty() || !trimmed.is_empty() {
+                rule.text.push_str(line.trim_end());
+                rule.text.push('\n');
+            }
+        }
+    }
+
+    for rule in &mut rules {
+        rule.text = rule.text.trim_end().to_string();
+    }
+
+    // section headings like "Core Rules" have no text of their own
+    rules.retain(|r| !r.text.is_empty());
+    rules
+}
+
+/// Splits `2.3. Title` or `C1 - Title` into the rule number and the title.
+fn split_rule_id(heading: &str) -> (Option<String>, String) {
+    let heading = heading.strip_prefix("Rule ").unwrap_or(heading);
+    let Some((id, title)) = heading.split_once(' ') else {
+        return (None, heading.to_string());
+    };
+
+    let id = id.trim_end_matches(['.', ':', ')']);
+    let is_id = !id.is_empty()
+        && id.chars().any(|c| c.is_ascii_digit())
+        && id.chars().all(|c| c.is_alphanumeric() || c == '.');
+    if !is_id {
+        return (None, heading.to_string());
+    }
+
+    let title = title.trim_start_matches(['-', '—', ':', ' ']).trim();
+    (Some(id.to_string()), title.to_string())
+}
+
+/// Splits a Markdown numbered list item like `1. Title` or `2.3) Title` into the number and the title.
+fn split_list_marker(line: &str) -> Option<(String, String)> {
+    let (marker, title) = line.split_once(' ')?;
+    let id = marker
+        .strip_suffix('.')
+        .or_else(|| marker.strip_suffix(')'))?;
+
+    let is_number = id.starts_with(|c: char| c.is_ascii_digit())
+        && id.chars().all(|c| c.is_ascii_digit() || c == '.');
+    if !is_number || title.trim().is_empty() {
+        return None;
+    }
+
+    Some((id.to_string(), title.trim().to_string()))
+}
+
+/// Removes XML tags and BBCode-like `[tag]` markup of the SS14 guidebook.
+fn strip_markup(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(start) = rest.find(['<', '[']) {
+        result.push_str(&rest[..start]);
+        let close = if rest[start..].starts_with('<') {
+            '>'
+        } else {
+            ']'
+        };
+
+        match rest[start..].find(close) {
+            // `[text](url)` is a Markdown link, not markup
+            Some(end)
+                if is_markup_tag(&rest[start + 1..start + end])
+                    && !rest[start + end + 1..].starts_with('(') =>
+            {
+                rest = &rest[start + end + 1..];
+            }
+            _ => {
+                result.push_str(&rest[start..start + 1]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn is_markup_tag(inner: &str) -> bool {
+    let name = inner.trim_start_matches('/');
+    let name = name
+        .split(['=', ' '])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('/');
+
+    MARKUP_TAGS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(rules: &[Rule]) -> Vec<Option<&str>> {
+        rules.iter().map(|r| r.id.as_deref()).collect()
+    }
+
+    #[test]
+    fn parses_markdown_headings() {
+        let rules = parse_rules(
+            "# Server Rules\n\
+             ## 1. Be respectful\n\
+             No insults.\n\
+             \n\
+             ## Rule 2: No metagaming\n\
+             [IC] Don't use information from outside the game.\n\
+             ## C1 - Command\n\
+             Follow the chain of command.\n",
+        );
+
+        assert_eq!(ids(&rules), [Some("1"), Some("2"), Some("C1")]);
+        assert_eq!(rules[0].title, "Be respectful");
+        assert_eq!(rules[0].text, "No insults.");
+        assert_eq!(rules[1].title, "No metagaming");
+        assert_eq!(
+            rules[1].text,
+            "[IC] Don't use information from outside the game."
+        );
+        assert_eq!(rules[2].title, "Command");
+    }
+
+    #[test]
+    fn parses_guidebook_document() {
+        let rules = parse_rules(
+            "<Document>\n\
+             # [color=#ff0000]Core Rules[/color]\n\
+             ## 1. [bold]No griefing[/bold]\n\
+             Don't destroy the station without a [italic]reason[/italic].\n\
+             See [textlink=\"Space Law\" link=\"SpaceLaw\"] and [the wiki](https://example.com).\n\
+             </Document>\n",
+        );
+
+        assert_eq!(ids(&rules), [Some("1")]);
+        assert_eq!(rules[0].title, "No griefing");
+        assert_eq!(
+            rules[0].text,
+            "Don't destroy the station without a reason.\n\
+             See  and [the wiki](https://example.com)."
+        );
+    }
+
+    #[test]
+    fn parses_numbered_list_items() {
+        let rules = parse_rules(
+            "# Rules\n\
+             1. Don't grief\n\
+             2) Don't metagame\n\
+             \x20\x203. Nested items belong to the rule above\n\
+             2.1. Don't powergame\n\
+             2024 changes are listed below.\n",
+        );
+
+        assert_eq!(ids(&rules), [Some("1"), Some("2"), Some("2.1")]);
+        assert_eq!(rules[1].title, "Don't metagame");
+        assert_eq!(
+            rules[1].text,
+            "Don't metagame\n  3. Nested items belong to the rule above"
+        );
+        assert_eq!(
+            rules[2].text,
+            "Don't powergame\n2024 changes are listed below."
+        );
+    }
+
+    #[test]
+    fn skips_headings_without_text() {
+        let rules =
+            parse_rules("# Core Rules\n\n## Roleplay Rules\n#\n## 1. Stay in character\nText\n");
+
+        assert_eq!(ids(&rules), [Some("1")]);
+        assert_eq!(rules[0].title, "Stay in character");
+    }
+
+    #[test]
+    fn splits_list_markers() {
+        assert_eq!(
+            split_list_marker("1. Title"),
+            Some(("1".to_string(), "Title".to_string()))
+        );
+        assert_eq!(
+            split_list_marker("2.3) Title"),
+            Some(("2.3".to_string(), "Title".to_string()))
+        );
+        assert_eq!(split_list_marker("2024 changes"), None);
+        assert_eq!(split_list_marker("a. Title"), None);
+        assert_eq!(split_list_marker("1."), None);
+        assert_eq!(split_list_marker("1. "), None);
+    }
+
+    #[test]
+    fn finds_rules_by_number_then_keyword() {
+        let rules = parse_rules("## 1. Be respectful\nNo insults.\n## 12. No griefing\nText\n");
+
+        assert_eq!(
+            find_rule(&rules, "1.").and_then(|r| r.id.as_deref()),
+            Some("1")
+        );
+        assert_eq!(
+            find_rule(&rules, "GRIEF").and_then(|r| r.id.as_deref()),
+            Some("12")
+        );
+        assert_eq!(
+            find_rule(&rules, "insults").and_then(|r| r.id.as_deref()),
+            Some("1")
+        );
+        assert!(find_rule(&rules, "missing").is_none());
+    }
+}
@@ -0,0 +1,305 @@
+use super::*;
+use crate::services::{
+    AdminActor, GameServer, GameServerService, SS14AuthClientService, SS14DatabaseService,
+    ServicesContainer,
+};
+use crate::utils::{gen_random_color, resolve_user_id, truncate_chars};
+use crate::{config_get, extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+const MAX_ANNOUNCEMENT_LENGTH: u16 = 1000;
+const MAX_PLAYERS_LENGTH: usize = 1000;
+/// Discord allows at most 25 choices per option.
+const MAX_CHOICES: usize = 25;
+
+#[derive(Debug)]
+pub struct ServerCommand {
+    game_servers: std::sync::Arc<GameServerService>,
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl ServerCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            game_servers: services.get_unsafe(),
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    async fn handle_info(&self, server: &GameServer, actor: &AdminActor) -> DiscordCommandResponse {
+        let info = try_discord_unwrap!(
+            self.game_servers.admin_info(server, actor).await,
+            error => "❌ An error occurred while requesting server info.",
+            log => "Failed to request admin info.",
+            ephemeral => true
+        );
+
+        let text = |value: Option<&serde_json::Value>| {
+            value
+                .map(|v| match v.as_str() {
+                    Some(s) => s.to_string(),
+                    None => v.to_string(),
+                })
+                .unwrap_or_else(|| "unknown".to_string())
+        };
+
+        let players: Vec<String> = info
+            .get("Players")
+            .and_then(|p| p.as_array())
+            .map(|players| {
+                players
+                    .iter()
+                    .filter_map(|p| p.get("Name").and_then(|n| n.as_str()))
+                    .map(|name| format!("`{}`", name))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        DiscordCommandResponse::followup_embed_response(
+            &format!(
+                "🖥️ **{}**\n**Round:** {}\n**Map:** {}\n**Preset:** {}\n**Players ({}):** {}",
+                server.name,
+                text(info.get("RoundId")),
+                text(info.pointer("/Map/Name")),
+                text(info.get("GamePreset")),
+                players.len(),
+                truncate_chars(&players.join(", "), MAX_PLAYERS_LENGTH)
+            ),
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for ServerCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("server", true, true).with_admin_flags(&["ADMIN"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        let server_option = || {
+            let mut option = CreateCommandOption::new(
+                CommandOptionType::String,
+                "server",
+                "Game server, the first one by default",
+            )
+            .name_localized("ru", "сервер")
+            .description_localized("ru", "Игровой сервер, по умолчанию первый");
+            for server in configured_server_names() {
+                option = option.add_string_choice(server, server);
+            }
+            option
+        };
+
+        CreateCommand::new("server")
+            .name_localized("ru", "сервер")
+            .description("Game server administration")
+            .description_localized("ru", "Администрирование игрового сервера")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "announce",
+                    "Sends an announcement to the server",
+                )
+                .name_localized("ru", "объявление")
+                .description_localized("ru", "Отправляет объявление на сервер")
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::String, "message", "Message")
+                        .name_localized("ru", "сообщение")
+                        .description_localized("ru", "Сообщение")
+                        .max_length(MAX_ANNOUNCEMENT_LENGTH)
+                        .required(true),
+                )
+                .add_sub_option(server_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::SubCommand, "kick", "Kicks a player")
+                    .name_localized("ru", "кик")
+                    .description_localized("ru", "Выгоняет игрока с сервера")
+                    .add_sub_option(
+                        CreateCommandOption::new(
+                            CommandOptionType::String,
+                            "login",
+                            "In-game login or UUID",
+                        )
+                        .set_autocomplete(true)
+                        .name_localized("ru", "логин")
+                        .description_localized("ru", "Внутриигровой логин или UUID")
+                        .required(true),
+                    )
+                    .add_sub_option(
+                        CreateCommandOption::new(CommandOptionType::String, "reason", "Reason")
+                            .name_localized("ru", "причина")
+                            .description_localized("ru", "Причина")
+                            .required(true),
+                    )
+                    .add_sub_option(server_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "restartround",
+                    "Restarts the current round",
+                )
+                .name_localized("ru", "рестарт_раунда")
+                .description_localized("ru", "Перезапускает текущий раунд")
+                .add_sub_option(server_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "info",
+                    "Shows round and players of the server",
+                )
+                .name_localized("ru", "инфо")
+                .description_localized("ru", "Показывает раунд и игроков сервера")
+                .add_sub_option(server_option()),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let sub =
+            try_discord_unwrap!(opts.first(), none => "No command supplied", ephemeral => true);
+        let sub_opts = match &sub.value {
+            ResolvedValue::SubCommand(opts) => opts,
+            _ => return DiscordCommandResponse::followup_response("No command supplied", true),
+        };
+
+        let server = match extract_discord_arg!(sub_opts, "server", String) {
+            Some(name) => self.game_servers.find_server(&name),
+            None => self.game_servers.servers().first(),
+        };
+        let server = try_discord_unwrap!(
+            server,
+            none => "No such game server configured.",
+            ephemeral => true
+        );
+
+        let user_id = try_discord_unwrap!(
+            self.ss14_client.get_user_id_from_discord(cmd.user.id.to_string()).await,
+            none => "🔍 Your Discord account is not linked to an SS14 account.",
+            error => "❌ An error occurred while fetching your UUID.",
+            log => "Failed to get admin UID by Discord ID.",
+            ephemeral => true
+        );
+        let name = try_discord_unwrap!(
+            self.ss14_db.get_login(user_id).await,
+            none => "❌ Your SS14 account has never joined the server.",
+            error => "❌ An error occurred while fetching your login.",
+            log => "Failed to get admin login.",
+            ephemeral => true
+        );
+        let actor = AdminActor { user_id, name };
+
+        let extra_flag = match sub.name {
+            "kick" => Some("BAN"),
+            "restartround" => Some("ROUND"),
+            _ => None,
+        };
+        if let Some(flag) = extra_flag {
+            let allowed = try_discord_unwrap!(
+                self.ss14_db.has_admin_flags(user_id, &[flag]).await,
+                error => "❌ An error occurred while checking your permissions.",
+                log => "Failed to check admin flags.",
+                ephemeral => true
+            );
+            if !allowed {
+                return DiscordCommandResponse::followup_response(
+                    &format!("⛔ This command requires the {} flag in game.", flag),
+                    true,
+                );
+            }
+        }
+
+        let result = match sub.name {
+            "info" => return self.handle_info(server, &actor).await,
+            "announce" => {
+                let message = try_discord_unwrap!(
+                    extract_discord_arg!(sub_opts, "message", String),
+                    none => "Message is not specified",
+                    ephemeral => true
+                );
+                self.game_servers
+                    .announce(server, &actor, &message)
+                    .await
+                    .map(|_| "📢 Announcement has been sent.".to_string())
+            }
+            "kick" => {
+                let login = try_discord_unwrap!(
+                    extract_discord_arg!(sub_opts, "login", String),
+                    none => "Login is not specified",
+                    ephemeral => true
+                );
+                let reason = try_discord_unwrap!(
+                    extract_discord_arg!(sub_opts, "reason", String),
+                    none => "Reason is not specified",
+                    ephemeral => true
+                );
+                let player = try_discord_unwrap!(
+                    resolve_user_id(&login, &self.ss14_client).await,
+                    none => "Such player doesn't exist.",
+                    error => "❌ An error occurred while fetching UUID.",
+                    log => "Failed to get UID by login.",
+                    ephemeral => true
+                );
+                self.game_servers
+                    .kick(server, &actor, player, &reason)
+                    .await
+                    .map(|_| format!("👢 `{}` has been kicked.", login))
+            }
+            "restartround" => self
+                .game_servers
+                .restart_round(server, &actor)
+                .await
+                .map(|_| "🔄 Round restart has been requested.".to_string()),
+            _ => return DiscordCommandResponse::followup_response("No command supplied", true),
+        };
+
+        let content = try_discord_unwrap!(
+            result,
+            error => "❌ Game server rejected the request.",
+            log => "Failed to call game server admin API.",
+            ephemeral => true
+        );
+
+        DiscordCommandResponse::followup_embed_response(
+            &format!("{} ({})", content, server.name),
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
+}
+
+/// Names of servers from `game_servers.servers`, used as option choices.
+fn configured_server_names() -> Vec<&'static str> {
+    let Some(servers) = config_get!("game_servers.servers", as_array) else {
+        return vec![];
+    };
+
+    servers
+        .iter()
+        .filter_map(|server| server.get_path("name")?.as_str())
+        .take(MAX_CHOICES)
+        .collect()
+}
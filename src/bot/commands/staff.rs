@@ -0,0 +1,94 @@
+use super::*;
+use crate::bot::tasks::staff_report::{build_activity_report, ACTIVITY_LEGEND};
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::{gen_random_color, parse_duration};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+const DEFAULT_PERIOD_DAYS: i64 = 7;
+
+#[derive(Debug)]
+pub struct StaffCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl StaffCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for StaffCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("staff", true, true).with_admin_flags(&["PERMISSIONS"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("staff")
+            .name_localized("ru", "стафф")
+            .description("Staff management tools")
+            .description_localized("ru", "Инструменты для управления составом")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "activity",
+                    "Shows admin activity",
+                )
+                .name_localized("ru", "активность")
+                .description_localized("ru", "Показывает активность администраторов")
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "period",
+                        "Period, e.g. 7d or 2w. One week by default",
+                    )
+                    .name_localized("ru", "период")
+                    .description_localized("ru", "Период, например 7d или 2w. По умолчанию неделя"),
+                ),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let sub =
+            try_discord_unwrap!(opts.first(), none => "No command supplied", ephemeral => true);
+        let sub_opts = match (sub.name, &sub.value) {
+            ("activity", ResolvedValue::SubCommand(opts)) => opts,
+            _ => return DiscordCommandResponse::followup_response("No command supplied", true),
+        };
+
+        let period = match extract_discord_arg!(sub_opts, "period", String) {
+            Some(period) => try_discord_unwrap!(
+                parse_duration(&period).filter(|d| !d.is_zero()),
+                none => "Invalid period. Use formats like 7d or 2w.",
+                ephemeral => true
+            ),
+            None => chrono::Duration::days(DEFAULT_PERIOD_DAYS),
+        };
+
+        let report = try_discord_unwrap!(
+            build_activity_report(&self.ss14_client, &self.ss14_db, chrono::Utc::now() - period).await,
+            error => "❌ An error occurred while collecting staff activity.",
+            log => "Failed to build staff activity report.",
+            ephemeral => true
+        );
+
+        DiscordCommandResponse::followup_embed_response(
+            &format!("📊 **Staff activity**\n{}", report),
+            Some(ACTIVITY_LEGEND),
+            Some(gen_random_color()),
+            true,
+        )
+    }
+}
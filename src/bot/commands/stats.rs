@@ -0,0 +1,147 @@
+use super::*;
+use crate::services::{BotDatabaseService, ServicesContainer};
+use crate::utils::{gen_random_color, sparkline};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use serenity::all::{CommandOptionType, CreateCommandOption};
+use serenity::async_trait;
+
+const HOUR_SECS: i64 = 3600;
+const DAY_SECS: i64 = 24 * HOUR_SECS;
+
+#[derive(Debug)]
+pub struct StatsCommand {
+    bot_db: std::sync::Arc<BotDatabaseService>,
+}
+
+impl StatsCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            bot_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for StatsCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("stats", true, false)
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("stats")
+            .name_localized("ru", "статистика")
+            .description("Shows server statistics")
+            .description_localized("ru", "Показывает статистику серверов")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "online",
+                    "Shows player count history",
+                )
+                .name_localized("ru", "онлайн")
+                .description_localized("ru", "Показывает историю онлайна")
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::String, "period", "Period")
+                        .name_localized("ru", "период")
+                        .description_localized("ru", "Период")
+                        .add_string_choice("Day", "day")
+                        .add_string_choice("Week", "week")
+                        .add_string_choice("Month", "month"),
+                ),
+            )
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let sub =
+            try_discord_unwrap!(opts.first(), none => "No command supplied", ephemeral => false);
+        let sub_opts = match (sub.name, &sub.value) {
+            ("online", ResolvedValue::SubCommand(opts)) => opts,
+            _ => return DiscordCommandResponse::followup_response("No command supplied", false),
+        };
+
+        // a day is drawn per hour, longer periods per day
+        let (period_name, buckets, bucket_secs) =
+            match extract_discord_arg!(sub_opts, "period", String).as_deref() {
+                Some("week") => ("last week", 7, DAY_SECS),
+                Some("month") => ("last month", 30, DAY_SECS),
+                _ => ("last day", 24, HOUR_SECS),
+            };
+
+        let now = chrono::Utc::now().timestamp();
+        // align buckets to whole hours or days so the chart doesn't shift between calls
+        let end = now - now % bucket_secs + bucket_secs;
+        let start = end - buckets * bucket_secs;
+
+        let samples = try_discord_unwrap!(
+            self.bot_db.get_player_count_samples(start).await,
+            error => "❌ An error occurred while fetching player counts.",
+            log => "Failed to fetch player count samples.",
+            ephemeral => false
+        );
+
+        if samples.is_empty() {
+            return DiscordCommandResponse::followup_embed_response(
+                &format!("No player counts recorded for the {}.", period_name),
+                None,
+                Some(gen_random_color()),
+                false,
+            );
+        }
+
+        let mut sums = vec![0i64; buckets as usize];
+        let mut counts = vec![0i64; buckets as usize];
+        for (time, players) in &samples {
+            let bucket = ((time - start) / bucket_secs) as usize;
+            if bucket < sums.len() {
+                sums[bucket] += players;
+                counts[bucket] += 1;
+            }
+        }
+        let averages: Vec<f64> = sums
+            .iter()
+            .zip(&counts)
+            .map(|(sum, count)| {
+                if *count > 0 {
+                    *sum as f64 / *count as f64
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        let (peak_time, peak) = samples
+            .iter()
+            .max_by_key(|(_, players)| *players)
+            .copied()
+            .unwrap_or_default();
+        let average =
+            samples.iter().map(|(_, players)| players).sum::<i64>() as f64 / samples.len() as f64;
+
+        let content = format!(
+            "📈 **Online for the {}**\n**Peak:** {} <t:{}:f>\n**Average:** {:.1}\n`{}`\n<t:{}:f> — <t:{}:f>",
+            period_name,
+            peak,
+            peak_time,
+            average,
+            sparkline(&averages),
+            start,
+            end
+        );
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            Some(if bucket_secs == HOUR_SECS {
+                "Average players per hour"
+            } else {
+                "Average players per day"
+            }),
+            Some(gen_random_color()),
+            false,
+        )
+    }
+}
@@ -0,0 +1,69 @@
+use super::*;
+use crate::services::{GameServerService, ServicesContainer};
+use crate::utils::gen_random_color;
+use serenity::async_trait;
+
+#[derive(Debug)]
+pub struct StatusCommand {
+    game_servers: std::sync::Arc<GameServerService>,
+}
+
+impl StatusCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            game_servers: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for StatusCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("status", true, false)
+    }
+
+    fn registration(&self) -> CreateCommand {
+        CreateCommand::new("status")
+            .name_localized("ru", "статус")
+            .description("Shows status of game servers")
+            .description_localized("ru", "Показывает состояние игровых серверов")
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        _opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let statuses = self.game_servers.poll().await;
+        if statuses.is_empty() {
+            return DiscordCommandResponse::followup_response("No game servers configured.", false);
+        }
+
+        let mut content = String::from("🖥️ **Game servers**\n");
+        for (server, status) in statuses {
+            match status {
+                Some(status) => content.push_str(&format!(
+                    "🟢 **{}**: {}/{} players, {}, map {}, round #{}\n",
+                    server.name,
+                    status.players,
+                    status.max_players,
+                    status.run_level.name(),
+                    status.map.as_deref().unwrap_or("unknown"),
+                    status
+                        .round_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_else(|| "-".to_string())
+                )),
+                None => content.push_str(&format!("🔴 **{}**: offline\n", server.name)),
+            }
+        }
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            None,
+            Some(gen_random_color()),
+            false,
+        )
+    }
+}
@@ -1,5 +1,5 @@
 use super::*;
-use crate::services::{SS14AuthClientService, ServicesContainer};
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
 use crate::try_discord_unwrap;
 use serenity::all::CommandOptionType;
 use serenity::async_trait;
@@ -8,12 +8,14 @@ use serenity::builder::CreateCommandOption;
 #[derive(Debug)]
 pub struct SummonCommand {
     ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
 }
 
 impl SummonCommand {
     pub fn new(services: &ServicesContainer) -> Self {
         Self {
             ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
         }
     }
 }
@@ -21,7 +23,7 @@ impl SummonCommand {
 #[async_trait]
 impl DiscordCommandHandler for SummonCommand {
     fn definition(&self) -> DiscordCommandDefinition {
-        DiscordCommandDefinition::new_global("summon", true, false)
+        DiscordCommandDefinition::new_global("summon", true, false).with_admin_flags(&["ADMIN"])
     }
 
     fn registration(&self) -> CreateCommand {
@@ -31,12 +33,18 @@ impl DiscordCommandHandler for SummonCommand {
             .description_localized("ru", "Пингует пользователя по игровому логину")
             .add_option(
                 CreateCommandOption::new(CommandOptionType::String, "login", "In-game login")
+                    .set_autocomplete(true)
                     .description_localized("ru", "Внутриигровой логин"),
             )
             .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
     }
 
-    async fn handler(&self, opts: &[ResolvedOption]) -> DiscordCommandResponse {
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
         let login = try_discord_unwrap!(
             opts_get_login(opts),
             none => "Login is not specified",
@@ -61,4 +69,13 @@ impl DiscordCommandHandler for SummonCommand {
 
         DiscordCommandResponse::followup_response(&format!("<@{}>", discord_id), false)
     }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
 }
@@ -1,5 +1,5 @@
 use super::*;
-use crate::services::{SS14AuthClientService, ServicesContainer};
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
 use crate::{extract_discord_arg, try_discord_unwrap};
 use log::error;
 use serenity::all::{Color, CommandOptionType, CreateCommand, CreateCommandOption, ResolvedOption};
@@ -8,12 +8,14 @@ use serenity::async_trait;
 #[derive(Debug)]
 pub struct UserIdCommand {
     ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
 }
 
 impl UserIdCommand {
     pub fn new(services: &ServicesContainer) -> Self {
         Self {
             ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
         }
     }
 }
@@ -21,22 +23,26 @@ impl UserIdCommand {
 #[async_trait]
 impl DiscordCommandHandler for UserIdCommand {
     fn definition(&self) -> DiscordCommandDefinition {
-        DiscordCommandDefinition::new_global("user_id", true, true)
+        DiscordCommandDefinition::new_global("user_id", true, true).with_admin_flags(&["ADMIN"])
     }
 
     fn registration(&self) -> CreateCommand {
         CreateCommand::new("user_id")
             .description("Fetches SS14 user ID by in-game login")
             .description_localized("ru", "Получает SS14 ID пользователя по игровому логину.")
-            .add_option(CreateCommandOption::new(
-                CommandOptionType::String,
-                "login",
-                "In-game login",
-            ))
+            .add_option(
+                CreateCommandOption::new(CommandOptionType::String, "login", "In-game login")
+                    .set_autocomplete(true),
+            )
             .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
     }
 
-    async fn handler(&self, opts: &[ResolvedOption]) -> DiscordCommandResponse {
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
         let mut login = try_discord_unwrap!(
             extract_discord_arg!(opts, "login", String),
             none => "Login is not specified",
@@ -60,4 +66,13 @@ impl DiscordCommandHandler for UserIdCommand {
             true,
         )
     }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
 }
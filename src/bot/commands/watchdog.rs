@@ -0,0 +1,319 @@
+use super::*;
+use crate::services::{GameServerService, ServicesContainer, WatchdogService};
+use crate::utils::{config_channel, gen_random_color, gen_random_uuid, RED_COLOR};
+use crate::{config_get, extract_discord_arg, try_discord_unwrap};
+use log::{error, info};
+use serenity::all::{
+    ButtonStyle, CommandOptionType, CreateActionRow, CreateButton, CreateCommandOption,
+    CreateMessage, EditInteractionResponse, UserId,
+};
+use serenity::async_trait;
+
+/// Discord allows at most 25 choices per option.
+const MAX_CHOICES: usize = 25;
+
+#[derive(Debug)]
+pub struct WatchdogCommand {
+    game_servers: std::sync::Arc<GameServerService>,
+    watchdog: std::sync::Arc<WatchdogService>,
+}
+
+impl WatchdogCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            game_servers: services.get_unsafe(),
+            watchdog: services.get_unsafe(),
+        }
+    }
+
+    async fn handle_status(&self, key: &str) -> DiscordCommandResponse {
+        let instance = try_discord_unwrap!(
+            self.watchdog.find_instance(key),
+            none => "No such watchdog instance configured.",
+            ephemeral => true
+        );
+
+        // the watchdog has no status endpoint, ask the game server it runs instead
+        let server = try_discord_unwrap!(
+            instance
+                .server
+                .as_deref()
+                .and_then(|name| self.game_servers.find_server(name)),
+            none => "Instance has no game server configured, status is unknown.",
+            ephemeral => true
+        );
+
+        let content = match self.game_servers.get_status(server).await {
+            Ok(status) => format!(
+                "🟢 `{}` is up: {}/{} players, {}",
+                instance.key,
+                status.players,
+                status.max_players,
+                status.run_level.name()
+            ),
+            Err(e) => {
+                error!("Failed to get status of {}: {}", server.name, e);
+                format!("🔴 `{}` is not responding.", instance.key)
+            }
+        };
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+
+    async fn run_action(&self, action: &str, key: &str) -> Result<(), crate::error::Error> {
+        let instance = self
+            .watchdog
+            .find_instance(key)
+            .ok_or_else(|| crate::error::Error::bot("No such watchdog instance configured"))?;
+
+        match action {
+            "restart" => self.watchdog.restart(instance).await,
+            "update" => self.watchdog.update(instance).await,
+            _ => Err(crate::error::Error::bot("Unknown watchdog action")),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for WatchdogCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("watchdog", true, true).with_admin_flags(&["SERVER"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        let instance_option = || {
+            let mut option =
+                CreateCommandOption::new(CommandOptionType::String, "instance", "Server instance")
+                    .name_localized("ru", "инстанс")
+                    .description_localized("ru", "Инстанс сервера")
+                    .required(true);
+            for key in configured_instance_keys() {
+                option = option.add_string_choice(key, key);
+            }
+            option
+        };
+
+        CreateCommand::new("watchdog")
+            .name_localized("ru", "вотчдог")
+            .description("Controls game server instances")
+            .description_localized("ru", "Управляет инстансами игровых серверов")
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "restart",
+                    "Restarts the server process",
+                )
+                .name_localized("ru", "перезапуск")
+                .description_localized("ru", "Перезапускает процесс сервера")
+                .add_sub_option(instance_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "update",
+                    "Updates the server to the latest build",
+                )
+                .name_localized("ru", "обновить")
+                .description_localized("ru", "Обновляет сервер до последней сборки")
+                .add_sub_option(instance_option()),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "status",
+                    "Shows whether the server is up",
+                )
+                .name_localized("ru", "статус")
+                .description_localized("ru", "Показывает, работает ли сервер")
+                .add_sub_option(instance_option()),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        _ctx: &Context,
+        cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let sub =
+            try_discord_unwrap!(opts.first(), none => "No command supplied", ephemeral => true);
+        let sub_opts = match &sub.value {
+            ResolvedValue::SubCommand(opts) => opts,
+            _ => return DiscordCommandResponse::followup_response("No command supplied", true),
+        };
+
+        let key = try_discord_unwrap!(
+            extract_discord_arg!(sub_opts, "instance", String),
+            none => "Instance is not specified",
+            ephemeral => true
+        );
+
+        let action = match sub.name {
+            "status" => return self.handle_status(&key).await,
+            "restart" | "update" => sub.name,
+            _ => return DiscordCommandResponse::followup_response("No command supplied", true),
+        };
+
+        if self.watchdog.find_instance(&key).is_none() {
+            return DiscordCommandResponse::followup_response(
+                "No such watchdog instance configured.",
+                true,
+            );
+        }
+
+        // the invoker is stored in the button so nobody else can confirm the action
+        let buttons = CreateActionRow::Buttons(vec![
+            CreateButton::new(format!(
+                "watchdog:confirm:{}:{}:{}",
+                action, key, cmd.user.id
+            ))
+            .label(format!("Confirm {}", action))
+            .style(ButtonStyle::Danger),
+            CreateButton::new(format!(
+                "watchdog:cancel:{}:{}:{}",
+                action, key, cmd.user.id
+            ))
+            .label("Cancel")
+            .style(ButtonStyle::Secondary),
+        ]);
+
+        DiscordCommandResponse::Followup(
+            CreateInteractionResponseFollowup::new()
+                .embed(
+                    CreateEmbed::new()
+                        .description(format!("⚠️ Are you sure you want to {} `{}`?", action, key))
+                        .color(RED_COLOR),
+                )
+                .components(vec![buttons])
+                .ephemeral(true),
+        )
+    }
+
+    async fn component(
+        &self,
+        ctx: &Context,
+        component: &ComponentInteraction,
+    ) -> Option<CreateInteractionResponse> {
+        let mut parts = component.data.custom_id.split(':').skip(1);
+        let verdict = parts.next()?;
+        let action = parts.next()?;
+        let key = parts.next()?;
+        let invoker = UserId::new(parts.next()?.parse().ok()?);
+
+        if component.user.id != invoker {
+            return Some(CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content("⛔ Only the admin who requested the action can confirm it.")
+                    .ephemeral(true),
+            ));
+        }
+
+        let message = |content: String| {
+            CreateInteractionResponseMessage::new()
+                .embed(
+                    CreateEmbed::new()
+                        .description(content)
+                        .color(gen_random_color()),
+                )
+                .components(vec![])
+        };
+
+        match verdict {
+            "cancel" => Some(CreateInteractionResponse::UpdateMessage(message(format!(
+                "Cancelled {} of `{}`.",
+                action, key
+            )))),
+            "confirm" => {
+                // the watchdog can take longer than the interaction deadline, acknowledge first
+                let pending = CreateInteractionResponse::UpdateMessage(message(format!(
+                    "⏳ Requesting {} of `{}`...",
+                    action, key
+                )));
+                if let Err(e) = component.create_response(&ctx.http, pending).await {
+                    error!("Failed to acknowledge watchdog confirmation: {}", e);
+                    return None;
+                }
+
+                let result = self.run_action(action, key).await;
+                let content = match &result {
+                    Ok(_) => format!("✅ `{}` {} has been requested.", key, action),
+                    Err(e) => {
+                        let err_id = gen_random_uuid();
+                        error!("{}. Failed to {} {}. Error: {}", err_id, action, key, e);
+                        format!(
+                            "❌ An error occurred while calling the watchdog.\nError ID: {}",
+                            err_id
+                        )
+                    }
+                };
+
+                let edit = EditInteractionResponse::new().embed(
+                    CreateEmbed::new()
+                        .description(content)
+                        .color(gen_random_color()),
+                );
+                if let Err(e) = component.edit_response(&ctx.http, edit).await {
+                    error!("Failed to report watchdog action result: {}", e);
+                }
+
+                audit(ctx, invoker, action, key, &result).await;
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Logs a confirmed watchdog action and posts it to `watchdog.audit_channel`.
+async fn audit(
+    ctx: &Context,
+    invoker: UserId,
+    action: &str,
+    key: &str,
+    result: &Result<(), crate::error::Error>,
+) {
+    let outcome = match result {
+        Ok(_) => "succeeded",
+        Err(_) => "failed",
+    };
+    info!("{} requested {} of {}: {}", invoker, action, key, outcome);
+
+    let Some(channel) = config_channel("watchdog.audit_channel") else {
+        return;
+    };
+
+    let message = CreateMessage::new().embed(
+        CreateEmbed::new()
+            .description(format!(
+                "🐕 <@{}> requested {} of `{}`, {}",
+                invoker, action, key, outcome
+            ))
+            .color(if result.is_ok() {
+                gen_random_color()
+            } else {
+                RED_COLOR
+            }),
+    );
+    if let Err(e) = channel.send_message(&ctx.http, message).await {
+        error!("Failed to post watchdog audit message: {}", e);
+    }
+}
+
+/// Keys of instances from `watchdog.instances`, used as option choices.
+fn configured_instance_keys() -> Vec<&'static str> {
+    let Some(instances) = config_get!("watchdog.instances", as_array) else {
+        return vec![];
+    };
+
+    instances
+        .iter()
+        .filter_map(|instance| instance.get_path("key")?.as_str())
+        .take(MAX_CHOICES)
+        .collect()
+}
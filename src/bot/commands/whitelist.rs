@@ -0,0 +1,379 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::{gen_random_color, resolve_user_id};
+use crate::{extract_discord_arg, try_discord_unwrap};
+use log::error;
+use serenity::all::{CommandOptionType, CreateCommandOption, RoleId, User, UserId};
+use serenity::async_trait;
+use uuid::Uuid;
+
+/// Discord limits embed descriptions to 4096 characters, leave some room for the truncation note.
+const MAX_LIST_LENGTH: usize = 3800;
+const MEMBERS_PAGE_SIZE: u64 = 1000;
+
+#[derive(Debug)]
+pub struct WhitelistCommand {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl WhitelistCommand {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    async fn resolve_target(
+        &self,
+        target: &WhitelistTarget,
+    ) -> Result<Option<Uuid>, crate::error::Error> {
+        match target {
+            WhitelistTarget::Login(login) => resolve_user_id(login, &self.ss14_client).await,
+            WhitelistTarget::Discord(user) => {
+                self.ss14_client
+                    .get_user_id_from_discord(user.id.to_string())
+                    .await
+            }
+        }
+    }
+
+    async fn handle_add_role(
+        &self,
+        ctx: &Context,
+        cmd: &CommandInteraction,
+        role: RoleId,
+    ) -> DiscordCommandResponse {
+        let guild_id = try_discord_unwrap!(
+            cmd.guild_id,
+            none => "This command is only available in guilds.",
+            ephemeral => true
+        );
+
+        let mut members: Vec<UserId> = vec![];
+        let mut after: Option<UserId> = None;
+        loop {
+            let page = try_discord_unwrap!(
+                guild_id.members(&ctx.http, Some(MEMBERS_PAGE_SIZE), after).await,
+                error => "❌ An error occurred while fetching guild members.",
+                log => "Failed to fetch guild members.",
+                ephemeral => true
+            );
+
+            after = page.last().map(|m| m.user.id);
+            members.extend(
+                page.iter()
+                    .filter(|m| m.roles.contains(&role))
+                    .map(|m| m.user.id),
+            );
+
+            if (page.len() as u64) < MEMBERS_PAGE_SIZE {
+                break;
+            }
+        }
+
+        let mut user_ids = Vec::with_capacity(members.len());
+        let mut unlinked = 0;
+        for member in &members {
+            match self
+                .ss14_client
+                .get_user_id_from_discord(member.to_string())
+                .await
+            {
+                Ok(Some(user_id)) => user_ids.push(user_id),
+                Ok(None) => unlinked += 1,
+                Err(e) => {
+                    error!("Failed to get UID of {} by Discord ID: {}", member, e);
+                    unlinked += 1;
+                }
+            }
+        }
+
+        let added = try_discord_unwrap!(
+            self.ss14_db.add_many_to_whitelist(&user_ids).await,
+            error => "❌ An error occurred while updating the whitelist.",
+            log => "Failed to bulk insert whitelist entries.",
+            ephemeral => true
+        );
+
+        DiscordCommandResponse::followup_embed_response(
+            &format!(
+                "✅ <@&{}> has {} member(s).\n**Newly whitelisted:** {}\n**Already whitelisted:** {}\n**Not linked:** {}",
+                role,
+                members.len(),
+                added,
+                user_ids.len() as u64 - added,
+                unlinked
+            ),
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+
+    async fn handle_list(&self) -> DiscordCommandResponse {
+        let entries = try_discord_unwrap!(
+            self.ss14_db.get_whitelist().await,
+            error => "❌ An error occurred while fetching the whitelist.",
+            log => "Failed to fetch whitelist.",
+            ephemeral => true
+        );
+
+        if entries.is_empty() {
+            return DiscordCommandResponse::followup_embed_response(
+                "Whitelist is empty.",
+                None,
+                Some(gen_random_color()),
+                true,
+            );
+        }
+
+        let mut content = format!("📜 **Whitelisted players:** {}\n", entries.len());
+        for (shown, (user_id, login)) in entries.iter().enumerate() {
+            let line = match login {
+                Some(login) => format!("`{}` ({})\n", login, user_id),
+                None => format!("{}\n", user_id),
+            };
+
+            if content.len() + line.len() > MAX_LIST_LENGTH {
+                content.push_str(&format!("...and {} more", entries.len() - shown));
+                break;
+            }
+            content.push_str(&line);
+        }
+
+        DiscordCommandResponse::followup_embed_response(
+            &content,
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+}
+
+#[async_trait]
+impl DiscordCommandHandler for WhitelistCommand {
+    fn definition(&self) -> DiscordCommandDefinition {
+        DiscordCommandDefinition::new_global("whitelist", true, true).with_admin_flags(&["BAN"])
+    }
+
+    fn registration(&self) -> CreateCommand {
+        let target_sub_command = |name: &str, description: &str| {
+            CreateCommandOption::new(CommandOptionType::SubCommand, name, description)
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::String,
+                        "login",
+                        "In-game login or UUID",
+                    )
+                    .set_autocomplete(true)
+                    .name_localized("ru", "логин")
+                    .description_localized("ru", "Внутриигровой логин или UUID"),
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::User,
+                        "user",
+                        "Linked Discord user",
+                    )
+                    .name_localized("ru", "пользователь")
+                    .description_localized("ru", "Привязанный пользователь Discord"),
+                )
+        };
+
+        CreateCommand::new("whitelist")
+            .name_localized("ru", "вайтлист")
+            .description("Manages the server whitelist")
+            .description_localized("ru", "Управляет вайтлистом сервера")
+            .add_option(
+                target_sub_command("add", "Adds a player to the whitelist")
+                    .name_localized("ru", "добавить")
+                    .description_localized("ru", "Добавляет игрока в вайтлист"),
+            )
+            .add_option(
+                target_sub_command("remove", "Removes a player from the whitelist")
+                    .name_localized("ru", "удалить")
+                    .description_localized("ru", "Удаляет игрока из вайтлиста"),
+            )
+            .add_option(
+                target_sub_command("check", "Checks whether a player is whitelisted")
+                    .name_localized("ru", "проверить")
+                    .description_localized("ru", "Проверяет, есть ли игрок в вайтлисте"),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "list",
+                    "Lists whitelisted players",
+                )
+                .name_localized("ru", "список")
+                .description_localized("ru", "Показывает игроков в вайтлисте"),
+            )
+            .add_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    "add_role",
+                    "Whitelists every linked member of a Discord role",
+                )
+                .name_localized("ru", "добавить_роль")
+                .description_localized(
+                    "ru",
+                    "Добавляет в вайтлист всех привязанных участников роли",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::Role, "role", "Discord role")
+                        .name_localized("ru", "роль")
+                        .description_localized("ru", "Роль Discord")
+                        .required(true),
+                ),
+            )
+            .default_member_permissions(MANAGE_WEBHOOKS_SERVER_PERMISSION)
+    }
+
+    async fn handler(
+        &self,
+        ctx: &Context,
+        cmd: &CommandInteraction,
+        opts: &[ResolvedOption],
+    ) -> DiscordCommandResponse {
+        let command = try_discord_unwrap!(
+            map_command(opts),
+            none => "Specify either in-game login, UUID or Discord user.",
+            ephemeral => true
+        );
+
+        let (target, action) = match command {
+            WhitelistSubCommand::List => return self.handle_list().await,
+            WhitelistSubCommand::AddRole(role) => {
+                return self.handle_add_role(ctx, cmd, role).await
+            }
+            WhitelistSubCommand::Add(target) => (target, WhitelistAction::Add),
+            WhitelistSubCommand::Remove(target) => (target, WhitelistAction::Remove),
+            WhitelistSubCommand::Check(target) => (target, WhitelistAction::Check),
+        };
+
+        let user_id = try_discord_unwrap!(
+            self.resolve_target(&target).await,
+            none => "🔍 Such player doesn't exist or is not linked.",
+            error => "❌ An error occurred while fetching UUID.",
+            log => "Failed to resolve whitelist target.",
+            ephemeral => true
+        );
+
+        let name = match self.ss14_db.get_login(user_id).await {
+            Ok(Some(login)) => format!("`{}`", login),
+            _ => user_id.to_string(),
+        };
+
+        let message = match action {
+            WhitelistAction::Add => {
+                let added = try_discord_unwrap!(
+                    self.ss14_db.add_to_whitelist(user_id).await,
+                    error => "❌ An error occurred while updating the whitelist.",
+                    log => "Failed to insert whitelist entry.",
+                    ephemeral => true
+                );
+                if added {
+                    format!("✅ {} has been whitelisted.", name)
+                } else {
+                    format!("{} is already whitelisted.", name)
+                }
+            }
+            WhitelistAction::Remove => {
+                let removed = try_discord_unwrap!(
+                    self.ss14_db.remove_from_whitelist(user_id).await,
+                    error => "❌ An error occurred while updating the whitelist.",
+                    log => "Failed to delete whitelist entry.",
+                    ephemeral => true
+                );
+                if removed {
+                    format!("✅ {} has been removed from the whitelist.", name)
+                } else {
+                    format!("{} is not whitelisted.", name)
+                }
+            }
+            WhitelistAction::Check => {
+                let whitelisted = try_discord_unwrap!(
+                    self.ss14_db.is_whitelisted(user_id).await,
+                    error => "❌ An error occurred while checking the whitelist.",
+                    log => "Failed to check whitelist entry.",
+                    ephemeral => true
+                );
+                if whitelisted {
+                    format!("✅ {} is whitelisted.", name)
+                } else {
+                    format!("❌ {} is not whitelisted.", name)
+                }
+            }
+        };
+
+        DiscordCommandResponse::followup_embed_response(
+            &message,
+            None,
+            Some(gen_random_color()),
+            true,
+        )
+    }
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _cmd: &CommandInteraction,
+        focused: AutocompleteOption<'_>,
+    ) -> Option<CreateAutocompleteResponse> {
+        login_autocomplete(&self.ss14_db, &focused).await
+    }
+}
+
+enum WhitelistTarget {
+    Login(String),
+    Discord(User),
+}
+
+enum WhitelistAction {
+    Add,
+    Remove,
+    Check,
+}
+
+enum WhitelistSubCommand {
+    Add(WhitelistTarget),
+    Remove(WhitelistTarget),
+    Check(WhitelistTarget),
+    List,
+    AddRole(RoleId),
+}
+
+fn map_command(opts: &[ResolvedOption]) -> Option<WhitelistSubCommand> {
+    let sub = opts.first()?;
+    let sub_opts = match &sub.value {
+        ResolvedValue::SubCommand(opts) => opts,
+        _ => return None,
+    };
+
+    let target = || {
+        if let Some(login) = extract_discord_arg!(sub_opts, "login", String) {
+            return Some(WhitelistTarget::Login(login));
+        }
+
+        sub_opts
+            .iter()
+            .find_map(|opt| match (opt.name, &opt.value) {
+                ("user", ResolvedValue::User(u, _)) => Some(WhitelistTarget::Discord((*u).clone())),
+                _ => None,
+            })
+    };
+
+    match sub.name {
+        "add" => Some(WhitelistSubCommand::Add(target()?)),
+        "remove" => Some(WhitelistSubCommand::Remove(target()?)),
+        "check" => Some(WhitelistSubCommand::Check(target()?)),
+        "list" => Some(WhitelistSubCommand::List),
+        "add_role" => {
+            let role = extract_discord_arg!(sub_opts, "role", Role)?;
+            Some(WhitelistSubCommand::AddRole(role.id))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,127 @@
+pub mod ahelp;
+pub mod ooc;
+pub mod round;
+
+pub use ahelp::AhelpEndpoint;
+pub use ooc::OocEndpoint;
+pub use round::RoundEndpoint;
+
+use crate::config_get;
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use log::{debug, error, info};
+use serenity::all::{Context, GatewayIntents, Message};
+use serenity::async_trait;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+/// Receives requests game servers post to `/<path>` of the bot HTTP server.
+#[async_trait]
+pub trait DiscordEndpointHandler: Send + Sync + std::fmt::Debug {
+    fn path(&self) -> &'static str;
+    async fn handle(&self, ctx: &Context, body: &[u8]) -> Result<(), crate::error::Error>;
+
+    /// Gateway intents the endpoint needs for [`DiscordEndpointHandler::message`].
+    fn intents(&self) -> GatewayIntents {
+        GatewayIntents::empty()
+    }
+
+    /// Called for every guild message, lets endpoints relay Discord replies back to the game.
+    async fn message(&self, _ctx: &Context, _message: &Message) {}
+}
+
+/// Serves endpoints on `http.listen` until the listener fails. Every request must carry
+/// `Authorization: Bearer <http.token>`.
+pub async fn serve(
+    ctx: Context,
+    endpoints: Vec<Arc<dyn DiscordEndpointHandler + Send + Sync>>,
+) -> Result<(), crate::error::Error> {
+    let Some(address) = config_get!("http.listen", as_str).filter(|a| !a.is_empty()) else {
+        return Ok(());
+    };
+
+    let listener = TcpListener::bind(address).await?;
+    info!("Listening for game server requests on {}", address);
+
+    let endpoints = Arc::new(endpoints);
+    loop {
+        let (stream, remote) = listener.accept().await?;
+        let endpoints = Arc::clone(&endpoints);
+        let ctx = ctx.clone();
+
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let endpoints = Arc::clone(&endpoints);
+                let ctx = ctx.clone();
+                async move { Ok::<_, Infallible>(route(&ctx, &endpoints, request).await) }
+            });
+
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!("Connection from {} failed: {}", remote, e);
+            }
+        });
+    }
+}
+
+async fn route(
+    ctx: &Context,
+    endpoints: &[Arc<dyn DiscordEndpointHandler + Send + Sync>],
+    request: Request<Incoming>,
+) -> Response<Full<Bytes>> {
+    if request.method() != Method::POST {
+        return respond(StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    let token = config_get!("http.token", as_str).unwrap_or_default();
+    let authorized = !token.is_empty()
+        && request
+            .headers()
+            .get(hyper::header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "))
+            == Some(token);
+    if !authorized {
+        return respond(StatusCode::UNAUTHORIZED);
+    }
+
+    let path = request.uri().path().trim_matches('/').to_string();
+    let Some(endpoint) = endpoints.iter().find(|e| e.path() == path) else {
+        return respond(StatusCode::NOT_FOUND);
+    };
+
+    let body = match Limited::new(request.into_body(), MAX_BODY_SIZE)
+        .collect()
+        .await
+    {
+        Ok(body) => body.to_bytes(),
+        Err(_) => return respond(StatusCode::PAYLOAD_TOO_LARGE),
+    };
+
+    match endpoint.handle(ctx, &body).await {
+        Ok(_) => respond(StatusCode::OK),
+        Err(crate::error::Error::DeserializationError(e)) => {
+            debug!("Malformed request to /{}: {}", path, e);
+            respond(StatusCode::BAD_REQUEST)
+        }
+        Err(e) => {
+            error!("Endpoint /{} failed: {}", path, e);
+            respond(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+fn respond(status: StatusCode) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::new()));
+    *response.status_mut() = status;
+    response
+}
@@ -0,0 +1,208 @@
+use super::*;
+use crate::services::{BotDatabaseService, ServicesContainer};
+use crate::utils::{config_channel, truncate_chars};
+use serde::{Deserialize, Serialize};
+use serenity::all::{
+    ChannelId, CreateAllowedMentions, CreateForumPost, CreateMessage, ReactionType,
+};
+use std::time::Duration;
+use uuid::Uuid;
+
+const REQWEST_TIMEOUT: u64 = 10;
+/// Discord limits message content to 2000 characters.
+const MAX_MESSAGE_LENGTH: usize = 1900;
+const MAX_THREAD_NAME_LENGTH: usize = 100;
+const DEFAULT_IGNORE_PREFIX: &str = "//";
+
+/// Ahelp message posted by the game server.
+#[derive(Debug, Deserialize)]
+struct AhelpMessage {
+    user_id: Uuid,
+    login: String,
+    message: String,
+    /// Name of the in-game admin who wrote the message, absent for messages of the player.
+    admin: Option<String>,
+    round_id: Option<i32>,
+}
+
+/// Staff reply sent to `ahelp.callback_url`.
+#[derive(Debug, Serialize)]
+struct AhelpReply<'a> {
+    user_id: Uuid,
+    author: &'a str,
+    message: &'a str,
+}
+
+/// Relays ahelps to one forum thread per player in `ahelp.forum_channel`
+/// and sends staff replies from those threads back to the game.
+#[derive(Debug)]
+pub struct AhelpEndpoint {
+    bot_db: std::sync::Arc<BotDatabaseService>,
+    client: reqwest::Client,
+}
+
+impl AhelpEndpoint {
+    pub fn new(services: &ServicesContainer) -> Result<Self, crate::error::Error> {
+        let client = reqwest::ClientBuilder::new()
+            .timeout(Duration::from_secs(REQWEST_TIMEOUT))
+            .build()?;
+
+        Ok(Self {
+            bot_db: services.get_unsafe(),
+            client,
+        })
+    }
+
+    /// Posts to the thread of the player, creating a new one if it doesn't exist or was deleted.
+    async fn relay(
+        &self,
+        ctx: &Context,
+        forum: ChannelId,
+        ahelp: &AhelpMessage,
+    ) -> Result<(), crate::error::Error> {
+        let content = match &ahelp.admin {
+            Some(admin) => format!("🛡️ **{}**: {}", admin, ahelp.message),
+            None => format!("**{}**: {}", ahelp.login, ahelp.message),
+        };
+        let message = || {
+            CreateMessage::new()
+                .content(truncate_chars(&content, MAX_MESSAGE_LENGTH))
+                .allowed_mentions(CreateAllowedMentions::new())
+        };
+
+        if let Some(thread) = self.bot_db.get_ahelp_thread(ahelp.user_id).await? {
+            match ChannelId::new(thread)
+                .send_message(&ctx.http, message())
+                .await
+            {
+                Ok(_) => return Ok(()),
+                Err(serenity::Error::Http(e))
+                    if e.status_code().map(|s| s.as_u16()) == Some(404) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let name = truncate_chars(
+            &format!("{} ({})", ahelp.login, ahelp.user_id),
+            MAX_THREAD_NAME_LENGTH,
+        );
+        let header = format!(
+            "Ahelp of `{}` ({}){}",
+            ahelp.login,
+            ahelp.user_id,
+            ahelp
+                .round_id
+                .map(|id| format!(", round #{}", id))
+                .unwrap_or_default()
+        );
+        let thread = forum
+            .create_forum_post(
+                &ctx.http,
+                CreateForumPost::new(
+                    name,
+                    CreateMessage::new()
+                        .content(header)
+                        .allowed_mentions(CreateAllowedMentions::new()),
+                ),
+            )
+            .await?;
+
+        self.bot_db
+            .set_ahelp_thread(ahelp.user_id, thread.id.get())
+            .await?;
+        thread.id.send_message(&ctx.http, message()).await?;
+
+        Ok(())
+    }
+
+    async fn send_reply(&self, reply: &AhelpReply<'_>) -> Result<(), crate::error::Error> {
+        let url = config_get!("ahelp.callback_url", as_str)
+            .filter(|u| !u.is_empty())
+            .ok_or_else(|| crate::error::Error::bot("Ahelp callback URL is not configured"))?;
+        let token = config_get!("ahelp.callback_token", as_str).unwrap_or_default();
+
+        self.client
+            .post(url)
+            .bearer_auth(token)
+            .json(reply)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscordEndpointHandler for AhelpEndpoint {
+    fn path(&self) -> &'static str {
+        "ahelp"
+    }
+
+    async fn handle(&self, ctx: &Context, body: &[u8]) -> Result<(), crate::error::Error> {
+        let ahelp: AhelpMessage = serde_json::from_slice(body)?;
+        let forum = config_channel("ahelp.forum_channel")
+            .ok_or_else(|| crate::error::Error::bot("Ahelp forum channel is not configured"))?;
+
+        self.relay(ctx, forum, &ahelp).await
+    }
+
+    fn intents(&self) -> GatewayIntents {
+        if config_channel("ahelp.forum_channel").is_some() {
+            GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT
+        } else {
+            GatewayIntents::empty()
+        }
+    }
+
+    async fn message(&self, ctx: &Context, message: &Message) {
+        if message.author.bot || message.content.is_empty() {
+            return;
+        }
+
+        // staff can discuss an ahelp in its thread without the player seeing it
+        let ignore_prefix =
+            config_get!("ahelp.ignore_prefix", as_str).unwrap_or(DEFAULT_IGNORE_PREFIX);
+        if !ignore_prefix.is_empty() && message.content.starts_with(ignore_prefix) {
+            return;
+        }
+
+        let user_id = match self.bot_db.get_ahelp_player(message.channel_id.get()).await {
+            Ok(Some(user_id)) => user_id,
+            Ok(None) => return,
+            Err(e) => {
+                error!(
+                    "Failed to look up ahelp thread {}: {}",
+                    message.channel_id, e
+                );
+                return;
+            }
+        };
+
+        let author = message
+            .member
+            .as_ref()
+            .and_then(|m| m.nick.as_deref())
+            .unwrap_or_else(|| message.author.display_name());
+        let reply = AhelpReply {
+            user_id,
+            author,
+            message: &message.content,
+        };
+
+        let reaction = match self.send_reply(&reply).await {
+            Ok(_) => '✅',
+            Err(e) => {
+                error!("Failed to relay ahelp reply to {}: {}", user_id, e);
+                '❌'
+            }
+        };
+
+        if let Err(e) = message
+            .react(&ctx.http, ReactionType::Unicode(reaction.to_string()))
+            .await
+        {
+            error!("Failed to react to ahelp reply: {}", e);
+        }
+    }
+}
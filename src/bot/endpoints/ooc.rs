@@ -0,0 +1,303 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::{config_channel, truncate_chars};
+use serde::{Deserialize, Serialize};
+use serenity::all::{Color, CreateAllowedMentions, CreateEmbed, CreateMessage, User};
+use std::time::Duration;
+use uuid::Uuid;
+
+const REQWEST_TIMEOUT: u64 = 10;
+/// Discord limits message content to 2000 characters.
+const MAX_MESSAGE_LENGTH: usize = 1900;
+/// Longer messages are cut before they are sent to the game.
+const MAX_GAME_MESSAGE_LENGTH: usize = 500;
+
+/// OOC message posted by the game server.
+#[derive(Debug, Deserialize)]
+struct OocMessage {
+    user_id: Option<Uuid>,
+    login: String,
+    message: String,
+    /// Sponsor OOC color of the player as `#rrggbb`.
+    color: Option<String>,
+}
+
+/// Discord message sent to `ooc.callback_url`.
+#[derive(Debug, Serialize)]
+struct OocReply<'a> {
+    author: &'a str,
+    message: &'a str,
+}
+
+/// Bridges OOC chat of the game with `ooc.channel`.
+#[derive(Debug)]
+pub struct OocEndpoint {
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    client: reqwest::Client,
+}
+
+impl OocEndpoint {
+    pub fn new(services: &ServicesContainer) -> Result<Self, crate::error::Error> {
+        let client = reqwest::ClientBuilder::new()
+            .timeout(Duration::from_secs(REQWEST_TIMEOUT))
+            .build()?;
+
+        Ok(Self {
+            ss14_db: services.get_unsafe(),
+            ss14_client: services.get_unsafe(),
+            client,
+        })
+    }
+
+    /// Name the player is shown under in Discord, with a non-pinging mention of their linked account.
+    async fn discord_name(&self, ooc: &OocMessage) -> String {
+        let Some(user_id) = ooc.user_id else {
+            return format!("**{}**", ooc.login);
+        };
+
+        match self.ss14_client.get_discord_id(user_id).await {
+            Ok(Some(discord_id)) => format!("**{}** (<@{}>)", ooc.login, discord_id),
+            Ok(None) => format!("**{}**", ooc.login),
+            Err(e) => {
+                debug!("Failed to get discord account of {}: {}", user_id, e);
+                format!("**{}**", ooc.login)
+            }
+        }
+    }
+
+    /// Name the author is shown under in game: their linked SS14 login, or Discord name otherwise.
+    async fn game_name(&self, message: &Message) -> String {
+        let discord_name = message
+            .member
+            .as_ref()
+            .and_then(|m| m.nick.as_deref())
+            .unwrap_or_else(|| message.author.display_name())
+            .to_string();
+
+        let user_id = match self
+            .ss14_client
+            .get_user_id_from_discord(message.author.id.to_string())
+            .await
+        {
+            Ok(Some(user_id)) => user_id,
+            Ok(None) => return discord_name,
+            Err(e) => {
+                debug!("Failed to get SS14 account of {}: {}", message.author.id, e);
+                return discord_name;
+            }
+        };
+
+        match self.ss14_db.get_login(user_id).await {
+            Ok(Some(login)) => login,
+            Ok(None) => discord_name,
+            Err(e) => {
+                debug!("Failed to get login of {}: {}", user_id, e);
+                discord_name
+            }
+        }
+    }
+
+    async fn send_reply(&self, reply: &OocReply<'_>) -> Result<(), crate::error::Error> {
+        let url = config_get!("ooc.callback_url", as_str)
+            .filter(|u| !u.is_empty())
+            .ok_or_else(|| crate::error::Error::bot("OOC callback URL is not configured"))?;
+        let token = config_get!("ooc.callback_token", as_str).unwrap_or_default();
+
+        self.client
+            .post(url)
+            .bearer_auth(token)
+            .json(reply)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscordEndpointHandler for OocEndpoint {
+    fn path(&self) -> &'static str {
+        "ooc"
+    }
+
+    async fn handle(&self, ctx: &Context, body: &[u8]) -> Result<(), crate::error::Error> {
+        let ooc: OocMessage = serde_json::from_slice(body)?;
+        let channel = config_channel("ooc.channel")
+            .ok_or_else(|| crate::error::Error::bot("OOC channel is not configured"))?;
+
+        let name = self.discord_name(&ooc).await;
+        let text = truncate_chars(&escape_mass_mentions(&ooc.message), MAX_MESSAGE_LENGTH);
+
+        // discord can't color text, so sponsors are shown as an embed of their color
+        let message = match ooc.color.as_deref().and_then(parse_color) {
+            Some(color) => CreateMessage::new().embed(
+                CreateEmbed::new()
+                    .description(format!("{}: {}", name, text))
+                    .color(color),
+            ),
+            None => CreateMessage::new().content(format!("{}: {}", name, text)),
+        };
+
+        channel
+            .send_message(
+                &ctx.http,
+                message.allowed_mentions(CreateAllowedMentions::new()),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn intents(&self) -> GatewayIntents {
+        if config_channel("ooc.channel").is_some() {
+            GatewayIntents::GUILD_MESSAGES | GatewayIntents::MESSAGE_CONTENT
+        } else {
+            GatewayIntents::empty()
+        }
+    }
+
+    async fn message(&self, _ctx: &Context, message: &Message) {
+        if message.author.bot || message.content.is_empty() {
+            return;
+        }
+
+        if config_channel("ooc.channel") != Some(message.channel_id) {
+            return;
+        }
+
+        let author = self.game_name(message).await;
+        let content = truncate_chars(
+            &sanitize_mentions(&message.content, &message.mentions),
+            MAX_GAME_MESSAGE_LENGTH,
+        );
+        let reply = OocReply {
+            author: &author,
+            message: &content,
+        };
+
+        if let Err(e) = self.send_reply(&reply).await {
+            error!("Failed to relay OOC message of {}: {}", author, e);
+        }
+    }
+}
+
+fn parse_color(s: &str) -> Option<Color> {
+    let hex = s.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+
+    u32::from_str_radix(hex, 16).ok().map(Color::new)
+}
+
+/// Keeps `@everyone` and `@here` from looking like real mentions.
+fn escape_mass_mentions(s: &str) -> String {
+    s.replace("@everyone", "@\u{200b}everyone")
+        .replace("@here", "@\u{200b}here")
+}
+
+/// Replaces Discord mention and emoji markup with readable text, since the game can't render it.
+fn sanitize_mentions(content: &str, mentions: &[User]) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find('<') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let inner = &rest[1..end];
+
+        let replacement = if let Some(id) = inner
+            .strip_prefix("@!")
+            .or_else(|| inner.strip_prefix('@'))
+            .filter(|id| id.chars().all(|c| c.is_ascii_digit()))
+        {
+            let name = mentions
+                .iter()
+                .find(|u| u.id.to_string() == id)
+                .map(|u| u.display_name())
+                .unwrap_or("unknown");
+            Some(format!("@{}", name))
+        } else if inner.starts_with("@&") {
+            Some("@role".to_string())
+        } else if inner.starts_with('#') {
+            Some("#channel".to_string())
+        } else if inner.starts_with(':') || inner.starts_with("a:") {
+            inner.split(':').nth(1).map(|name| format!(":{}:", name))
+        } else {
+            None
+        };
+
+        match replacement {
+            Some(replacement) => {
+                result.push_str(&replacement);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('<');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+        .replace("@everyone", "everyone")
+        .replace("@here", "here")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serenity::all::UserId;
+
+    fn user(id: u64, name: &str) -> User {
+        let mut user = User::default();
+        user.id = UserId::new(id);
+        user.name = name.to_string();
+        user
+    }
+
+    #[test]
+    fn replaces_user_mentions() {
+        let mentions = [user(123, "alice")];
+
+        assert_eq!(
+            sanitize_mentions("hi <@123> and <@!123>", &mentions),
+            "hi @alice and @alice"
+        );
+        assert_eq!(sanitize_mentions("hi <@456>", &mentions), "hi @unknown");
+    }
+
+    #[test]
+    fn replaces_role_channel_and_emoji_markup() {
+        assert_eq!(
+            sanitize_mentions("<@&1> in <#2> <:pog:3> <a:dance:4>", &[]),
+            "@role in #channel :pog: :dance:"
+        );
+    }
+
+    #[test]
+    fn keeps_text_that_is_not_markup() {
+        assert_eq!(sanitize_mentions("1 < 2 > 0", &[]), "1 < 2 > 0");
+        assert_eq!(sanitize_mentions("a <b> c", &[]), "a <b> c");
+        assert_eq!(
+            sanitize_mentions("unterminated <@123", &[]),
+            "unterminated <@123"
+        );
+        assert_eq!(sanitize_mentions("<<@123>", &[user(123, "bob")]), "<@bob");
+    }
+
+    #[test]
+    fn removes_mass_mentions() {
+        assert_eq!(
+            sanitize_mentions("@everyone look @here", &[]),
+            "everyone look here"
+        );
+    }
+}
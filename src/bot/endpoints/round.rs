@@ -0,0 +1,133 @@
+use super::*;
+use crate::services::{SS14DatabaseService, ServicesContainer};
+use crate::utils::{config_channel, format_duration, truncate_chars};
+use serde::Deserialize;
+use serenity::all::{Color, CreateAllowedMentions, CreateEmbed, CreateMessage, RoleId, Timestamp};
+
+const START_COLOR: Color = Color::from_rgb(87, 242, 135);
+const END_COLOR: Color = Color::from_rgb(88, 101, 242);
+/// Discord limits embed descriptions to 4096 characters.
+const MAX_SUMMARY_LENGTH: usize = 3800;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RoundEvent {
+    Start,
+    End,
+}
+
+/// Round start or end posted by the game server. Missing details are taken from the `round` table.
+#[derive(Debug, Deserialize)]
+struct RoundMessage {
+    event: RoundEvent,
+    round_id: i32,
+    server: Option<String>,
+    map: Option<String>,
+    players: Option<i64>,
+    /// Round duration in seconds, only sent on round end.
+    duration: Option<i64>,
+    /// Round end summary, e.g. the round end report or notable events.
+    summary: Option<String>,
+}
+
+/// Announces round starts and ends in `rounds.channel`.
+#[derive(Debug)]
+pub struct RoundEndpoint {
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl RoundEndpoint {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    async fn build_embed(&self, round: &RoundMessage) -> Result<CreateEmbed, crate::error::Error> {
+        let record = self.ss14_db.get_round(round.round_id).await?;
+
+        let server = round
+            .server
+            .clone()
+            .or_else(|| record.as_ref().and_then(|r| r.server_name.clone()));
+        let players = round
+            .players
+            .or_else(|| record.as_ref().map(|r| r.player_count));
+        let start_date = record.as_ref().and_then(|r| r.start_date);
+
+        let (title, color) = match round.event {
+            RoundEvent::Start => (format!("Round #{} started", round.round_id), START_COLOR),
+            RoundEvent::End => (format!("Round #{} ended", round.round_id), END_COLOR),
+        };
+
+        let mut embed = CreateEmbed::new()
+            .title(title)
+            .color(color)
+            .timestamp(Timestamp::now());
+
+        if let Some(server) = server {
+            embed = embed.field("Server", server, true);
+        }
+
+        if let Some(map) = &round.map {
+            embed = embed.field("Map", map, true);
+        }
+
+        if let Some(players) = players {
+            embed = embed.field("Players", players.to_string(), true);
+        }
+
+        if let RoundEvent::End = round.event {
+            let duration = round
+                .duration
+                .map(chrono::Duration::seconds)
+                .or_else(|| start_date.map(|start| chrono::Utc::now() - start));
+            if let Some(duration) = duration {
+                embed = embed.field("Duration", format_duration(duration), true);
+            }
+
+            if let Some(summary) = round.summary.as_deref().filter(|s| !s.trim().is_empty()) {
+                embed = embed.description(truncate_chars(summary, MAX_SUMMARY_LENGTH));
+            }
+        }
+
+        Ok(embed)
+    }
+}
+
+#[async_trait]
+impl DiscordEndpointHandler for RoundEndpoint {
+    fn path(&self) -> &'static str {
+        "round"
+    }
+
+    async fn handle(&self, ctx: &Context, body: &[u8]) -> Result<(), crate::error::Error> {
+        let round: RoundMessage = serde_json::from_slice(body)?;
+        let channel = config_channel("rounds.channel")
+            .ok_or_else(|| crate::error::Error::bot("Rounds channel is not configured"))?;
+
+        let mut message = CreateMessage::new()
+            .embed(self.build_embed(&round).await?)
+            .allowed_mentions(CreateAllowedMentions::new());
+
+        if let RoundEvent::Start = round.event {
+            if let Some(role) = start_role() {
+                message = message
+                    .content(format!("<@&{}>", role))
+                    .allowed_mentions(CreateAllowedMentions::new().roles([role]));
+            }
+        }
+
+        channel.send_message(&ctx.http, message).await?;
+
+        Ok(())
+    }
+}
+
+/// Role pinged on round start, `"0"` means nobody is pinged.
+fn start_role() -> Option<RoleId> {
+    config_get!("rounds.start_role", as_str)
+        .and_then(|id| id.parse::<u64>().ok())
+        .filter(|id| *id != 0)
+        .map(RoleId::new)
+}
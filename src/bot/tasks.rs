@@ -0,0 +1,29 @@
+pub mod admin_sync;
+pub mod ban_feed;
+pub mod changelog;
+pub mod connection_watcher;
+pub mod playtime_roles;
+pub mod server_status;
+pub mod staff_report;
+pub mod stats_prune;
+
+pub use admin_sync::AdminSyncTask;
+pub use ban_feed::BanFeedTask;
+pub use changelog::ChangelogTask;
+pub use connection_watcher::ConnectionWatcherTask;
+pub use playtime_roles::PlaytimeRolesTask;
+pub use server_status::ServerStatusTask;
+pub use staff_report::StaffReportTask;
+pub use stats_prune::StatsPruneTask;
+
+use serenity::all::Context;
+use serenity::async_trait;
+use std::time::Duration;
+
+/// Periodic job running in background once the bot is connected to discord.
+#[async_trait]
+pub trait DiscordTaskHandler: Send + Sync + std::fmt::Debug {
+    fn name(&self) -> &'static str;
+    fn interval(&self) -> Duration;
+    async fn run(&self, ctx: &Context) -> Result<(), crate::error::Error>;
+}
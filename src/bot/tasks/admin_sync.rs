@@ -0,0 +1,200 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::{config_get, config_get_array};
+use log::{error, info};
+use serenity::all::{GuildId, Http, RoleId, UserId};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 600;
+const MEMBERS_PAGE_SIZE: u64 = 1000;
+
+/// A change of the in-game `admin` table needed to match Discord roles.
+#[derive(Debug)]
+pub struct AdminRankChange {
+    pub user_id: Uuid,
+    /// Current rank, `None` if the player is not an admin yet.
+    pub from: Option<String>,
+    /// Rank according to Discord roles, `None` if the admin should be removed.
+    pub to: Option<String>,
+}
+
+/// Keeps SS14 admin ranks in line with Discord roles mapped in `admin_sync.ranks`.
+/// Only admins holding one of the mapped ranks are touched, anyone else is managed by hand.
+#[derive(Debug)]
+pub struct AdminSyncTask {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl AdminSyncTask {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordTaskHandler for AdminSyncTask {
+    fn name(&self) -> &'static str {
+        "admin_sync"
+    }
+
+    fn interval(&self) -> Duration {
+        let secs = config_get!("admin_sync.poll_interval_secs", as_int)
+            .and_then(|s| u64::try_from(s).ok())
+            .filter(|s| *s > 0)
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+        Duration::from_secs(secs)
+    }
+
+    async fn run(&self, ctx: &Context) -> Result<(), crate::error::Error> {
+        if !config_get!("admin_sync.enabled", as_bool).unwrap_or(false) {
+            return Ok(());
+        }
+
+        let changes = plan_admin_sync(&ctx.http, &self.ss14_client, &self.ss14_db).await?;
+        // failed changes are logged, there is nobody to show them to
+        apply_admin_sync(&self.ss14_db, &changes).await?;
+        Ok(())
+    }
+}
+
+/// Compares Discord roles of linked members with the `admin` table without changing anything.
+pub async fn plan_admin_sync(
+    http: &Http,
+    ss14_client: &SS14AuthClientService,
+    ss14_db: &SS14DatabaseService,
+) -> Result<Vec<AdminRankChange>, crate::error::Error> {
+    let ranks = configured_ranks();
+    if ranks.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut member_roles: HashMap<UserId, Vec<RoleId>> = HashMap::new();
+    let guilds = config_get_array!("discord.guilds", as_array, as_str).unwrap_or_default();
+    for guild_id in guilds.into_iter().filter_map(|id| id.parse::<u64>().ok()) {
+        let guild_id = GuildId::new(guild_id);
+        let mut after: Option<UserId> = None;
+        loop {
+            let page = guild_id
+                .members(http, Some(MEMBERS_PAGE_SIZE), after)
+                .await?;
+            after = page.last().map(|m| m.user.id);
+
+            for member in page.iter().filter(|m| !m.user.bot) {
+                member_roles
+                    .entry(member.user.id)
+                    .or_default()
+                    .extend(member.roles.iter().copied());
+            }
+
+            if (page.len() as u64) < MEMBERS_PAGE_SIZE {
+                break;
+            }
+        }
+    }
+
+    // config order decides which rank wins when a member has several mapped roles
+    let mut desired: HashMap<Uuid, String> = HashMap::new();
+    for (member, roles) in member_roles {
+        let Some((_, rank)) = ranks.iter().find(|(role, _)| roles.contains(role)) else {
+            continue;
+        };
+
+        if let Some(user_id) = ss14_client
+            .get_user_id_from_discord(member.to_string())
+            .await?
+        {
+            desired.insert(user_id, rank.clone());
+        }
+    }
+
+    let is_managed = |rank: &Option<String>| {
+        rank.as_ref()
+            .is_some_and(|rank| ranks.iter().any(|(_, managed)| managed == rank))
+    };
+
+    let current: HashMap<Uuid, Option<String>> =
+        ss14_db.get_admin_ranks().await?.into_iter().collect();
+    let mut changes = vec![];
+
+    for (user_id, rank) in &current {
+        if is_managed(rank) && !desired.contains_key(user_id) {
+            changes.push(AdminRankChange {
+                user_id: *user_id,
+                from: rank.clone(),
+                to: None,
+            });
+        }
+    }
+
+    for (user_id, rank) in desired {
+        let from = match current.get(&user_id) {
+            None => None,
+            Some(from) if from.as_ref() == Some(&rank) => continue,
+            Some(from) if from.is_none() || is_managed(from) => from.clone(),
+            // hand-assigned ranks are left alone
+            Some(_) => continue,
+        };
+
+        changes.push(AdminRankChange {
+            user_id,
+            from,
+            to: Some(rank),
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Applies `changes` and returns those which failed because the rank doesn't exist in game.
+pub async fn apply_admin_sync<'a>(
+    ss14_db: &SS14DatabaseService,
+    changes: &'a [AdminRankChange],
+) -> Result<Vec<&'a AdminRankChange>, crate::error::Error> {
+    let mut failed = vec![];
+
+    for change in changes {
+        match &change.to {
+            Some(rank) => {
+                if ss14_db.set_admin_rank(change.user_id, rank).await? {
+                    info!("Set admin rank of {} to {}", change.user_id, rank);
+                } else {
+                    error!("Admin rank {} doesn't exist in game", rank);
+                    failed.push(change);
+                }
+            }
+            None => {
+                ss14_db.remove_admin(change.user_id).await?;
+                info!("Removed admin {}", change.user_id);
+            }
+        }
+    }
+
+    Ok(failed)
+}
+
+/// Reads `admin_sync.ranks` as Discord role to rank name pairs, skipping unconfigured roles.
+fn configured_ranks() -> Vec<(RoleId, String)> {
+    let Some(ranks) = config_get!("admin_sync.ranks", as_array) else {
+        return vec![];
+    };
+
+    ranks
+        .iter()
+        .filter_map(|rank| {
+            let role = rank
+                .get_path("role")?
+                .as_str()?
+                .parse::<u64>()
+                .ok()
+                .filter(|id| *id != 0)?;
+            let name = rank.get_path("rank")?.as_str()?;
+
+            Some((RoleId::new(role), name.to_string()))
+        })
+        .collect()
+}
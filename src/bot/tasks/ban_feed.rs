@@ -0,0 +1,215 @@
+use super::*;
+use crate::services::{
+    BanKind, BanRecord, BotDatabaseService, SS14DatabaseService, ServicesContainer,
+};
+use crate::utils::{config_channel, format_duration, truncate_chars, RED_COLOR};
+use crate::{config_get, config_get_array};
+use serenity::all::{ChannelId, CreateEmbed, CreateEmbedFooter, CreateMessage, Timestamp};
+
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 60;
+const BANS_BATCH_SIZE: i64 = 100;
+/// Time a ban gets to be committed together with all rows of its department and all lower ids.
+const GRACE_PERIOD_SECS: i64 = 120;
+const MAX_FIELD_LENGTH: usize = 1024;
+const REDACTED: &str = "*redacted*";
+
+/// Posts new server and role bans to the ban log channel.
+#[derive(Debug)]
+pub struct BanFeedTask {
+    bot_db: std::sync::Arc<BotDatabaseService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl BanFeedTask {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            bot_db: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    async fn process(
+        &self,
+        ctx: &Context,
+        channel: ChannelId,
+        kind: BanKind,
+    ) -> Result<(), crate::error::Error> {
+        let mark_name = match kind {
+            BanKind::Server => "ban_feed.server_ban",
+            BanKind::Role => "ban_feed.server_role_ban",
+        };
+
+        // first run starts from the latest ban instead of reposting the whole history
+        let Some(after_id) = self.bot_db.get_high_water_mark(mark_name).await? else {
+            let latest = self.ss14_db.get_latest_ban_id(kind).await?.unwrap_or(0);
+            self.bot_db
+                .set_high_water_mark(mark_name, latest.into())
+                .await?;
+            return Ok(());
+        };
+
+        let mut bans = self
+            .ss14_db
+            .get_bans_after(kind, after_id as i32, BANS_BATCH_SIZE)
+            .await?;
+        let full_batch = bans.len() as i64 == BANS_BATCH_SIZE;
+
+        // ids of concurrent transactions can become visible out of order, and a department ban
+        // is inserted row by row, so only bans older than the grace period are posted
+        let cutoff = chrono::Utc::now() - chrono::Duration::seconds(GRACE_PERIOD_SECS);
+        let ready = bans.iter().take_while(|b| b.ban_time <= cutoff).count();
+        let held_back = ready < bans.len();
+        bans.truncate(ready);
+
+        let mut groups = group_bans(bans);
+        // a full batch can end in the middle of a department, the next poll starts from it
+        if full_batch && !held_back && groups.len() > 1 {
+            groups.pop();
+        }
+
+        let include_hidden = config_get!("ban_feed.include_hidden", as_bool).unwrap_or(false);
+        for group in groups {
+            let last_id = group.last().map(|b| b.id).unwrap_or_default();
+
+            if include_hidden || !group[0].hidden {
+                channel
+                    .send_message(&ctx.http, CreateMessage::new().embed(build_embed(&group)))
+                    .await?;
+            }
+
+            self.bot_db
+                .set_high_water_mark(mark_name, last_id.into())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscordTaskHandler for BanFeedTask {
+    fn name(&self) -> &'static str {
+        "ban_feed"
+    }
+
+    fn interval(&self) -> Duration {
+        let secs = config_get!("ban_feed.poll_interval_secs", as_int)
+            .and_then(|s| u64::try_from(s).ok())
+            .filter(|s| *s > 0)
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+        Duration::from_secs(secs)
+    }
+
+    async fn run(&self, ctx: &Context) -> Result<(), crate::error::Error> {
+        let Some(channel) = config_channel("ban_feed.channel") else {
+            return Ok(());
+        };
+
+        self.process(ctx, channel, BanKind::Server).await?;
+        self.process(ctx, channel, BanKind::Role).await?;
+
+        Ok(())
+    }
+}
+
+/// Department role bans are inserted as one row per job, merge them back into a single post.
+fn group_bans(bans: Vec<BanRecord>) -> Vec<Vec<BanRecord>> {
+    let mut groups: Vec<Vec<BanRecord>> = vec![];
+
+    for ban in bans {
+        if let Some(group) = groups.last_mut() {
+            let first = &group[0];
+            if ban.role_id.is_some()
+                && first.player_user_id == ban.player_user_id
+                && first.ban_time == ban.ban_time
+                && first.reason == ban.reason
+            {
+                group.push(ban);
+                continue;
+            }
+        }
+
+        groups.push(vec![ban]);
+    }
+
+    groups
+}
+
+fn build_embed(group: &[BanRecord]) -> CreateEmbed {
+    let redacted = config_get_array!("ban_feed.redact", as_array, as_str).unwrap_or_default();
+    let redact = |field: &str, value: String| {
+        if redacted.contains(&field) {
+            REDACTED.to_string()
+        } else {
+            value
+        }
+    };
+
+    let ban = &group[0];
+    let duration = match ban.expiration_time {
+        Some(expiration) => format_duration(expiration - ban.ban_time),
+        None => "Permanent".to_string(),
+    };
+
+    let title = if ban.role_id.is_some() {
+        "🚫 Role ban"
+    } else {
+        "🔨 Server ban"
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(title)
+        .color(RED_COLOR)
+        .field(
+            "Player",
+            redact(
+                "player",
+                format!("`{}`", ban.player_login.as_deref().unwrap_or("unknown")),
+            ),
+            true,
+        )
+        .field(
+            "Admin",
+            redact(
+                "admin",
+                format!("`{}`", ban.admin_login.as_deref().unwrap_or("unknown")),
+            ),
+            true,
+        )
+        .field("Duration", duration, true)
+        .field(
+            "Round",
+            redact(
+                "round",
+                ban.round_id
+                    .map(|r| r.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            true,
+        )
+        .timestamp(Timestamp::from(ban.ban_time))
+        .footer(CreateEmbedFooter::new(format!(
+            "Ban ID: {}",
+            group
+                .iter()
+                .map(|b| b.id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+
+    if ban.role_id.is_some() {
+        let roles = group
+            .iter()
+            .filter_map(|b| b.role_id.as_deref())
+            .map(|r| format!("`{}`", r.split_once(':').map(|(_, r)| r).unwrap_or(r)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        embed = embed.field("Roles", truncate_chars(&roles, MAX_FIELD_LENGTH), false);
+    }
+
+    embed.field(
+        "Reason",
+        redact("reason", truncate_chars(&ban.reason, MAX_FIELD_LENGTH)),
+        false,
+    )
+}
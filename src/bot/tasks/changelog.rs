@@ -0,0 +1,205 @@
+use super::*;
+use crate::config_get;
+use crate::services::{BotDatabaseService, ServicesContainer};
+use crate::utils::{config_channel, truncate_chars};
+use serde::Deserialize;
+use serenity::all::{ChannelId, Color, CreateEmbed, CreateMessage};
+
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 300;
+const MARK_NAME: &str = "changelog.last_entry";
+const CHANGELOG_COLOR: Color = Color::from_rgb(88, 101, 242);
+/// Discord limits embed descriptions to 4096 characters.
+const MAX_DESCRIPTION_LENGTH: usize = 3800;
+/// Discord limits embed titles to 256 characters.
+const MAX_TITLE_LENGTH: usize = 256;
+/// Discord allows at most 10 embeds per message.
+const MAX_EMBEDS_PER_MESSAGE: usize = 10;
+/// Discord limits the total text of all embeds in a message to 6000 characters.
+const MAX_EMBED_TEXT_PER_MESSAGE: usize = 6000;
+
+/// `Resources/Changelog/Changelog.yml` of an SS14 checkout.
+#[derive(Debug, Deserialize)]
+struct Changelog {
+    #[serde(rename = "Entries", default)]
+    entries: Vec<ChangelogEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangelogEntry {
+    id: i64,
+    author: String,
+    #[serde(default)]
+    changes: Vec<Change>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Change {
+    #[serde(rename = "type")]
+    kind: String,
+    message: String,
+}
+
+/// Posts new entries of the SS14 changelog at `changelog.path` to the changelog channel.
+#[derive(Debug)]
+pub struct ChangelogTask {
+    bot_db: std::sync::Arc<BotDatabaseService>,
+}
+
+impl ChangelogTask {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            bot_db: services.get_unsafe(),
+        }
+    }
+
+    /// Posts `entries` as one message, one embed per author, and advances the mark past them.
+    async fn post(
+        &self,
+        ctx: &Context,
+        channel: ChannelId,
+        entries: &[ChangelogEntry],
+    ) -> Result<(), crate::error::Error> {
+        let embeds = group_by_author(entries)
+            .iter()
+            .map(|group| build_embed(group))
+            .collect();
+        channel
+            .send_message(&ctx.http, CreateMessage::new().embeds(embeds))
+            .await?;
+
+        if let Some(last_id) = entries.iter().map(|e| e.id).max() {
+            self.bot_db.set_high_water_mark(MARK_NAME, last_id).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscordTaskHandler for ChangelogTask {
+    fn name(&self) -> &'static str {
+        "changelog"
+    }
+
+    fn interval(&self) -> Duration {
+        let secs = config_get!("changelog.poll_interval_secs", as_int)
+            .and_then(|s| u64::try_from(s).ok())
+            .filter(|s| *s > 0)
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+        Duration::from_secs(secs)
+    }
+
+    async fn run(&self, ctx: &Context) -> Result<(), crate::error::Error> {
+        let Some(channel) = config_channel("changelog.channel") else {
+            return Ok(());
+        };
+        let Some(path) = config_get!("changelog.path", as_str).filter(|p| !p.is_empty()) else {
+            return Ok(());
+        };
+
+        let changelog: Changelog = serde_yaml::from_str(&tokio::fs::read_to_string(path).await?)?;
+        let latest = changelog.entries.iter().map(|e| e.id).max().unwrap_or(0);
+
+        // first run starts from the latest entry instead of reposting the whole changelog
+        let Some(after_id) = self.bot_db.get_high_water_mark(MARK_NAME).await? else {
+            self.bot_db.set_high_water_mark(MARK_NAME, latest).await?;
+            return Ok(());
+        };
+
+        let mut entries: Vec<_> = changelog
+            .entries
+            .into_iter()
+            .filter(|e| e.id > after_id && !e.changes.is_empty())
+            .collect();
+        entries.sort_by_key(|e| e.id);
+
+        // batches follow id order, so the mark never passes an entry that wasn't posted
+        let mut batch: Vec<ChangelogEntry> = vec![];
+        for entry in entries {
+            batch.push(entry);
+            if batch.len() > 1 && !fits_in_message(&batch) {
+                let entry = batch.pop().unwrap();
+                self.post(ctx, channel, &batch).await?;
+                batch = vec![entry];
+            }
+        }
+        if !batch.is_empty() {
+            self.post(ctx, channel, &batch).await?;
+        }
+
+        // entries without changes are skipped, but shouldn't be looked at again either
+        if latest > after_id {
+            self.bot_db.set_high_water_mark(MARK_NAME, latest).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Groups entries by author, keeping authors in order of their first entry.
+fn group_by_author(entries: &[ChangelogEntry]) -> Vec<Vec<&ChangelogEntry>> {
+    let mut groups: Vec<Vec<&ChangelogEntry>> = vec![];
+
+    for entry in entries {
+        match groups.iter_mut().find(|g| g[0].author == entry.author) {
+            Some(group) => group.push(entry),
+            None => groups.push(vec![entry]),
+        }
+    }
+
+    groups
+}
+
+/// Checks Discord limits on embed count and total embed text of a single message.
+fn fits_in_message(entries: &[ChangelogEntry]) -> bool {
+    let groups = group_by_author(entries);
+    let length: usize = groups
+        .iter()
+        .map(|group| {
+            let (title, description) = embed_text(group);
+            title.chars().count() + description.chars().count()
+        })
+        .sum();
+
+    groups.len() <= MAX_EMBEDS_PER_MESSAGE && length <= MAX_EMBED_TEXT_PER_MESSAGE
+}
+
+fn change_icon(kind: &str) -> &'static str {
+    match kind {
+        "Add" => "🆕",
+        "Remove" => "❌",
+        "Fix" => "🐛",
+        "Tweak" => "⚒️",
+        _ => "🔹",
+    }
+}
+
+/// Title and description of the embed of one author.
+fn embed_text(entries: &[&ChangelogEntry]) -> (String, String) {
+    let mut lines = vec![];
+
+    for entry in entries {
+        for change in &entry.changes {
+            let line = format!("{} {}", change_icon(&change.kind), change.message.trim());
+            lines.push(match &entry.url {
+                Some(url) => format!("{} ([PR]({}))", line, url),
+                None => line,
+            });
+        }
+    }
+
+    (
+        truncate_chars(&entries[0].author, MAX_TITLE_LENGTH),
+        truncate_chars(&lines.join("\n"), MAX_DESCRIPTION_LENGTH),
+    )
+}
+
+fn build_embed(entries: &[&ChangelogEntry]) -> CreateEmbed {
+    let (title, description) = embed_text(entries);
+
+    CreateEmbed::new()
+        .title(title)
+        .description(description)
+        .color(CHANGELOG_COLOR)
+}
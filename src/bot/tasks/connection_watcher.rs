@@ -0,0 +1,149 @@
+use super::*;
+use crate::config_get;
+use crate::services::{
+    BotDatabaseService, Connection, RemarkKind, SS14DatabaseService, ServicesContainer,
+};
+use crate::utils::{alts_lookback_days, config_channel, truncate_chars, RED_COLOR};
+use serenity::all::{CreateEmbed, CreateEmbedFooter, CreateMessage};
+
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 30;
+const CONNECTIONS_BATCH_SIZE: i64 = 100;
+const MARK_NAME: &str = "connection_watcher.last_connection";
+const MAX_FIELD_LENGTH: usize = 1024;
+
+/// Polls `connection_log` and alerts staff when a watchlisted player
+/// or a suspected alt of a banned account connects.
+#[derive(Debug)]
+pub struct ConnectionWatcherTask {
+    bot_db: std::sync::Arc<BotDatabaseService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl ConnectionWatcherTask {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            bot_db: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    async fn build_alert(
+        &self,
+        connection: &Connection,
+    ) -> Result<Option<CreateEmbed>, crate::error::Error> {
+        let watchlists: Vec<String> = self
+            .ss14_db
+            .get_admin_remarks(connection.user_id)
+            .await?
+            .into_iter()
+            .filter(|r| r.kind == RemarkKind::Watchlist)
+            .map(|r| format!("`#{}` {}", r.id, r.message))
+            .collect();
+
+        let since = chrono::Utc::now() - chrono::Duration::days(alts_lookback_days());
+        let banned_alts = self.ss14_db.find_banned_alts(connection, since).await?;
+
+        if watchlists.is_empty() && banned_alts.is_empty() {
+            return Ok(None);
+        }
+
+        let mut embed = CreateEmbed::new()
+            .title("👁️ Watched player connected")
+            .description(format!(
+                "`{}` ({}) connected to **{}** <t:{}:R>{}",
+                connection.user_name,
+                connection.user_id,
+                connection
+                    .server_name
+                    .as_deref()
+                    .unwrap_or("unknown server"),
+                connection.time.timestamp(),
+                if connection.denied {
+                    ", connection was denied"
+                } else {
+                    ""
+                }
+            ))
+            .color(RED_COLOR)
+            .footer(CreateEmbedFooter::new(format!(
+                "Connection #{}",
+                connection.id
+            )));
+
+        if !watchlists.is_empty() {
+            embed = embed.field(
+                "Watchlist",
+                truncate_chars(&watchlists.join("\n"), MAX_FIELD_LENGTH),
+                false,
+            );
+        }
+
+        if !banned_alts.is_empty() {
+            let mut alts = Vec::with_capacity(banned_alts.len());
+            for alt in banned_alts {
+                let login = self.ss14_db.get_login(alt).await?;
+                alts.push(format!(
+                    "`{}` ({})",
+                    login.as_deref().unwrap_or("unknown"),
+                    alt
+                ));
+            }
+            embed = embed.field(
+                "Shares IP or HWID with banned",
+                truncate_chars(&alts.join("\n"), MAX_FIELD_LENGTH),
+                false,
+            );
+        }
+
+        Ok(Some(embed))
+    }
+}
+
+#[async_trait]
+impl DiscordTaskHandler for ConnectionWatcherTask {
+    fn name(&self) -> &'static str {
+        "connection_watcher"
+    }
+
+    fn interval(&self) -> Duration {
+        let secs = config_get!("watcher.poll_interval_secs", as_int)
+            .and_then(|s| u64::try_from(s).ok())
+            .filter(|s| *s > 0)
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+        Duration::from_secs(secs)
+    }
+
+    async fn run(&self, ctx: &Context) -> Result<(), crate::error::Error> {
+        let Some(channel) = config_channel("watcher.alert_channel") else {
+            return Ok(());
+        };
+
+        // first run starts from the latest connection instead of alerting on the whole history
+        let Some(after_id) = self.bot_db.get_high_water_mark(MARK_NAME).await? else {
+            let latest = self.ss14_db.get_latest_connection_id().await?.unwrap_or(0);
+            self.bot_db
+                .set_high_water_mark(MARK_NAME, latest.into())
+                .await?;
+            return Ok(());
+        };
+
+        let connections = self
+            .ss14_db
+            .get_connections_after(after_id as i32, CONNECTIONS_BATCH_SIZE)
+            .await?;
+
+        for connection in connections {
+            if let Some(embed) = self.build_alert(&connection).await? {
+                channel
+                    .send_message(&ctx.http, CreateMessage::new().embed(embed))
+                    .await?;
+            }
+
+            self.bot_db
+                .set_high_water_mark(MARK_NAME, connection.id.into())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
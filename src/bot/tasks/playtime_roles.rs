@@ -0,0 +1,220 @@
+use super::*;
+use crate::services::{SS14AuthClientService, SS14DatabaseService, ServicesContainer};
+use crate::utils::configured_departments;
+use crate::{config_get, config_get_array};
+use log::{error, info};
+use serenity::all::{GuildId, Member, RoleId, UserId};
+
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 3600;
+const MEMBERS_PAGE_SIZE: u64 = 1000;
+const OVERALL_TRACKER: &str = "Overall";
+const JOB_TRACKER_PREFIX: &str = "Job";
+
+/// Discord role granted once a player reaches `hours` of playtime, overall,
+/// in a single playtime tracker or summed over the jobs of a department.
+#[derive(Debug)]
+struct Milestone {
+    role: RoleId,
+    hours: i64,
+    /// Playtime tracker as stored in `play_time`, e.g. `JobStalkerGuide`. Takes precedence over `department`.
+    tracker: Option<String>,
+    department: Option<String>,
+}
+
+/// Grants milestone roles from `playtime_roles.milestones` to linked members
+/// and takes them away from members who no longer qualify or unlinked their account.
+#[derive(Debug)]
+pub struct PlaytimeRolesTask {
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl PlaytimeRolesTask {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+
+    /// Roles `member` should have. `None` means the lookup failed and the member should be left alone.
+    async fn earned_roles(&self, member: &Member, milestones: &[Milestone]) -> Option<Vec<RoleId>> {
+        let user_id = match self
+            .ss14_client
+            .get_user_id_from_discord(member.user.id.to_string())
+            .await
+        {
+            Ok(Some(user_id)) => user_id,
+            Ok(None) => return Some(vec![]),
+            Err(e) => {
+                error!(
+                    "Failed to get UID of {} by Discord ID: {}",
+                    member.user.id, e
+                );
+                return None;
+            }
+        };
+
+        let playtime = match self.ss14_db.get_playtime(user_id).await {
+            Ok(playtime) => playtime,
+            Err(e) => {
+                error!("Failed to fetch playtime of {}: {}", user_id, e);
+                return None;
+            }
+        };
+
+        let departments = configured_departments();
+        let earned = milestones
+            .iter()
+            .filter(|milestone| {
+                let seconds: i64 = match (&milestone.tracker, &milestone.department) {
+                    (Some(tracker), _) => playtime
+                        .iter()
+                        .find(|(t, _)| t == tracker)
+                        .map(|(_, seconds)| *seconds)
+                        .unwrap_or(0),
+                    (None, Some(department)) => {
+                        let trackers: Vec<String> = departments
+                            .iter()
+                            .find(|(name, _)| name.eq_ignore_ascii_case(department))
+                            .map(|(_, jobs)| jobs.as_slice())
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|job| format!("{}{}", JOB_TRACKER_PREFIX, job))
+                            .collect();
+                        playtime
+                            .iter()
+                            .filter(|(tracker, _)| trackers.contains(tracker))
+                            .map(|(_, seconds)| seconds)
+                            .sum()
+                    }
+                    (None, None) => playtime
+                        .iter()
+                        .find(|(tracker, _)| tracker == OVERALL_TRACKER)
+                        .map(|(_, seconds)| *seconds)
+                        .unwrap_or(0),
+                };
+
+                seconds >= milestone.hours * 3600
+            })
+            .map(|milestone| milestone.role)
+            .collect();
+
+        Some(earned)
+    }
+
+    async fn reconcile(
+        &self,
+        ctx: &Context,
+        guild_id: GuildId,
+        milestones: &[Milestone],
+    ) -> Result<(), crate::error::Error> {
+        let mut after: Option<UserId> = None;
+        loop {
+            let page = guild_id
+                .members(&ctx.http, Some(MEMBERS_PAGE_SIZE), after)
+                .await?;
+            after = page.last().map(|m| m.user.id);
+
+            for member in page.iter().filter(|m| !m.user.bot) {
+                let Some(earned) = self.earned_roles(member, milestones).await else {
+                    continue;
+                };
+
+                for milestone in milestones {
+                    let has = member.roles.contains(&milestone.role);
+                    let should_have = earned.contains(&milestone.role);
+
+                    // a single member failing, e.g. above the bot in role hierarchy, must not stop the rest
+                    if should_have && !has {
+                        match member.add_role(&ctx.http, milestone.role).await {
+                            Ok(_) => info!("Granted role {} to {}", milestone.role, member.user.id),
+                            Err(e) => error!(
+                                "Failed to grant role {} to {}: {}",
+                                milestone.role, member.user.id, e
+                            ),
+                        }
+                    } else if !should_have && has {
+                        match member.remove_role(&ctx.http, milestone.role).await {
+                            Ok(_) => {
+                                info!("Removed role {} from {}", milestone.role, member.user.id)
+                            }
+                            Err(e) => error!(
+                                "Failed to remove role {} from {}: {}",
+                                milestone.role, member.user.id, e
+                            ),
+                        }
+                    }
+                }
+            }
+
+            if (page.len() as u64) < MEMBERS_PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DiscordTaskHandler for PlaytimeRolesTask {
+    fn name(&self) -> &'static str {
+        "playtime_roles"
+    }
+
+    fn interval(&self) -> Duration {
+        let secs = config_get!("playtime_roles.poll_interval_secs", as_int)
+            .and_then(|s| u64::try_from(s).ok())
+            .filter(|s| *s > 0)
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+        Duration::from_secs(secs)
+    }
+
+    async fn run(&self, ctx: &Context) -> Result<(), crate::error::Error> {
+        let milestones = configured_milestones();
+        if milestones.is_empty() {
+            return Ok(());
+        }
+
+        let guilds = config_get_array!("discord.guilds", as_array, as_str).unwrap_or_default();
+        for guild_id in guilds.into_iter().filter_map(|id| id.parse::<u64>().ok()) {
+            self.reconcile(ctx, GuildId::new(guild_id), &milestones)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads `playtime_roles.milestones`, skipping entries without a configured role.
+fn configured_milestones() -> Vec<Milestone> {
+    let Some(milestones) = config_get!("playtime_roles.milestones", as_array) else {
+        return vec![];
+    };
+
+    milestones
+        .iter()
+        .filter_map(|milestone| {
+            let role = milestone
+                .get_path("role")?
+                .as_str()?
+                .parse::<u64>()
+                .ok()
+                .filter(|id| *id != 0)?;
+
+            Some(Milestone {
+                role: RoleId::new(role),
+                hours: i64::from(milestone.get_path("hours")?.as_int()?),
+                tracker: milestone
+                    .get_path("tracker")
+                    .and_then(|t| t.as_str())
+                    .map(str::to_string),
+                department: milestone
+                    .get_path("department")
+                    .and_then(|d| d.as_str())
+                    .map(str::to_string),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,80 @@
+use super::*;
+use crate::config_get;
+use crate::services::{BotDatabaseService, GameServerService, ServicesContainer};
+use serenity::all::{ActivityData, OnlineStatus};
+
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 30;
+
+/// Polls game servers, shows their online in the bot presence and records player count samples.
+#[derive(Debug)]
+pub struct ServerStatusTask {
+    bot_db: std::sync::Arc<BotDatabaseService>,
+    game_servers: std::sync::Arc<GameServerService>,
+}
+
+impl ServerStatusTask {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            bot_db: services.get_unsafe(),
+            game_servers: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordTaskHandler for ServerStatusTask {
+    fn name(&self) -> &'static str {
+        "server_status"
+    }
+
+    fn interval(&self) -> Duration {
+        let secs = config_get!("game_servers.poll_interval_secs", as_int)
+            .and_then(|s| u64::try_from(s).ok())
+            .filter(|s| *s > 0)
+            .unwrap_or(DEFAULT_POLL_INTERVAL_SECS);
+        Duration::from_secs(secs)
+    }
+
+    async fn run(&self, ctx: &Context) -> Result<(), crate::error::Error> {
+        if self.game_servers.servers().is_empty() {
+            return Ok(());
+        }
+
+        let statuses = self.game_servers.poll().await;
+        let now = chrono::Utc::now().timestamp();
+
+        let online: Vec<_> = statuses
+            .into_iter()
+            .filter_map(|(server, status)| Some((server, status?)))
+            .collect();
+
+        if online.is_empty() {
+            ctx.set_presence(
+                Some(ActivityData::watching("servers go down")),
+                OnlineStatus::DoNotDisturb,
+            );
+        } else {
+            let activity: Vec<String> = online
+                .iter()
+                .map(|(server, status)| {
+                    format!(
+                        "{}/{} on {}",
+                        status.players, status.max_players, server.name
+                    )
+                })
+                .collect();
+            ctx.set_presence(
+                Some(ActivityData::playing(activity.join(" | "))),
+                OnlineStatus::Online,
+            );
+        }
+
+        for (server, status) in &online {
+            self.bot_db
+                .add_player_count_sample(&server.name, now, status.players.into())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
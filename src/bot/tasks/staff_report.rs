@@ -0,0 +1,138 @@
+use super::*;
+use crate::config_get_array;
+use crate::services::{
+    BotDatabaseService, SS14AuthClientService, SS14DatabaseService, ServicesContainer,
+};
+use crate::utils::{config_channel, format_playtime, gen_random_color};
+use log::error;
+use serenity::all::{CreateEmbed, CreateEmbedFooter, CreateMessage};
+
+/// Checked hourly, the report itself goes out once per `REPORT_PERIOD_DAYS`.
+const CHECK_INTERVAL_SECS: u64 = 3600;
+const REPORT_PERIOD_DAYS: i64 = 7;
+const LAST_REPORT_MARK: &str = "staff_report.last_report";
+const DEFAULT_ADMIN_TRACKER: &str = "AdminTime";
+/// Discord limits embed descriptions to 4096 characters, leave some room for the truncation note.
+const MAX_LIST_LENGTH: usize = 3800;
+
+/// Posts a weekly admin activity summary to `staff_report.channel`.
+#[derive(Debug)]
+pub struct StaffReportTask {
+    bot_db: std::sync::Arc<BotDatabaseService>,
+    ss14_client: std::sync::Arc<SS14AuthClientService>,
+    ss14_db: std::sync::Arc<SS14DatabaseService>,
+}
+
+impl StaffReportTask {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            bot_db: services.get_unsafe(),
+            ss14_client: services.get_unsafe(),
+            ss14_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordTaskHandler for StaffReportTask {
+    fn name(&self) -> &'static str {
+        "staff_report"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(CHECK_INTERVAL_SECS)
+    }
+
+    async fn run(&self, ctx: &Context) -> Result<(), crate::error::Error> {
+        let Some(channel) = config_channel("staff_report.channel") else {
+            return Ok(());
+        };
+
+        let now = chrono::Utc::now();
+        let period = chrono::Duration::days(REPORT_PERIOD_DAYS);
+
+        // first run only schedules the report instead of posting right after startup
+        let Some(last_report) = self.bot_db.get_high_water_mark(LAST_REPORT_MARK).await? else {
+            self.bot_db
+                .set_high_water_mark(LAST_REPORT_MARK, now.timestamp())
+                .await?;
+            return Ok(());
+        };
+
+        if now.timestamp() - last_report < period.num_seconds() {
+            return Ok(());
+        }
+
+        let report = build_activity_report(&self.ss14_client, &self.ss14_db, now - period).await?;
+        channel
+            .send_message(
+                &ctx.http,
+                CreateMessage::new().embed(
+                    CreateEmbed::new()
+                        .title("📊 Weekly staff activity")
+                        .description(report)
+                        .footer(CreateEmbedFooter::new(ACTIVITY_LEGEND))
+                        .color(gen_random_color()),
+                ),
+            )
+            .await?;
+
+        self.bot_db
+            .set_high_water_mark(LAST_REPORT_MARK, now.timestamp())
+            .await?;
+
+        Ok(())
+    }
+}
+
+pub const ACTIVITY_LEGEND: &str =
+    "📜 log entries involving them, 🔨 bans, 📝 notes in period, ⏱️ total admin time";
+
+/// Lists activity of every admin since `since`, one line per admin.
+pub async fn build_activity_report(
+    ss14_client: &SS14AuthClientService,
+    ss14_db: &SS14DatabaseService,
+    since: chrono::DateTime<chrono::Utc>,
+) -> Result<String, crate::error::Error> {
+    let trackers: Vec<String> = config_get_array!("staff_report.admin_trackers", as_array, as_str)
+        .filter(|trackers| !trackers.is_empty())
+        .unwrap_or_else(|| vec![DEFAULT_ADMIN_TRACKER])
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+    let activity = ss14_db.get_staff_activity(since, &trackers).await?;
+    if activity.is_empty() {
+        return Ok("No admins found.".to_string());
+    }
+
+    let mut content = format!("Since <t:{}:d>\n", since.timestamp());
+    for (shown, admin) in activity.iter().enumerate() {
+        let discord = match ss14_client.get_discord_id(admin.user_id).await {
+            Ok(Some(id)) => format!(" <@{}>", id),
+            Ok(None) => String::new(),
+            Err(e) => {
+                error!("Failed to get Discord ID of {}: {}", admin.user_id, e);
+                String::new()
+            }
+        };
+
+        let line = format!(
+            "`{}`{}: 📜 {} 🔨 {} 📝 {} ⏱️ {}\n",
+            admin.login.as_deref().unwrap_or("unknown"),
+            discord,
+            admin.log_entries,
+            admin.bans,
+            admin.notes,
+            format_playtime(admin.admin_playtime)
+        );
+
+        if content.len() + line.len() > MAX_LIST_LENGTH {
+            content.push_str(&format!("...and {} more", activity.len() - shown));
+            break;
+        }
+        content.push_str(&line);
+    }
+
+    Ok(content)
+}
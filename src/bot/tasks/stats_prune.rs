@@ -0,0 +1,50 @@
+use super::*;
+use crate::config_get;
+use crate::services::{BotDatabaseService, ServicesContainer};
+use log::info;
+
+const PRUNE_INTERVAL_SECS: u64 = 24 * 3600;
+const DEFAULT_RETENTION_DAYS: i64 = 90;
+
+/// Removes player count samples older than `stats.retention_days`.
+#[derive(Debug)]
+pub struct StatsPruneTask {
+    bot_db: std::sync::Arc<BotDatabaseService>,
+}
+
+impl StatsPruneTask {
+    pub fn new(services: &ServicesContainer) -> Self {
+        Self {
+            bot_db: services.get_unsafe(),
+        }
+    }
+}
+
+#[async_trait]
+impl DiscordTaskHandler for StatsPruneTask {
+    fn name(&self) -> &'static str {
+        "stats_prune"
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(PRUNE_INTERVAL_SECS)
+    }
+
+    async fn run(&self, _ctx: &Context) -> Result<(), crate::error::Error> {
+        let retention_days = config_get!("stats.retention_days", as_int)
+            .map(i64::from)
+            .filter(|d| *d > 0)
+            .unwrap_or(DEFAULT_RETENTION_DAYS);
+        let before = chrono::Utc::now() - chrono::Duration::days(retention_days);
+
+        let pruned = self
+            .bot_db
+            .prune_player_count_samples(before.timestamp())
+            .await?;
+        if pruned > 0 {
+            info!("Pruned {} player count samples", pruned);
+        }
+
+        Ok(())
+    }
+}
@@ -6,10 +6,12 @@ pub enum Error {
     IoError(#[from] std::io::Error),
     #[error("Deserialize error: {0}")]
     DeserializationError(#[from] serde_json::Error),
+    #[error("YAML error: {0}")]
+    YamlError(#[from] serde_yaml::Error),
     #[error("Discord bot error: {0}")]
     BotError(String),
     #[error("Discord API error: {0}")]
-    SerenityError(#[from] serenity::Error),
+    SerenityError(Box<serenity::Error>),
     #[error("Type mismatch error: {0}")]
     ParseIntError(#[from] std::num::ParseIntError),
     #[error("Database error: {0}")]
@@ -24,6 +26,12 @@ pub enum Error {
     TypeAuthdError(String),
 }
 
+impl From<serenity::Error> for Error {
+    fn from(e: serenity::Error) -> Self {
+        Self::SerenityError(Box::new(e))
+    }
+}
+
 impl Error {
     pub fn bot(s: &str) -> Self {
         Self::BotError(s.to_string())
@@ -10,7 +10,10 @@ pub use config::CONFIG;
 pub use error::Error;
 
 pub async fn initialize_services(container: &services::ServicesContainer) -> Result<(), Error> {
-    use services::{BotDatabaseService, SS14AuthClientService, SS14DatabaseService};
+    use services::{
+        BotDatabaseService, GameServer, GameServerService, SS14AuthClientService,
+        SS14DatabaseService, WatchdogInstance, WatchdogService,
+    };
     let bot_db_path = config_get!("database.bot_database_path", as_str).unwrap();
 
     let db_service =
@@ -29,6 +32,49 @@ pub async fn initialize_services(container: &services::ServicesContainer) -> Res
         ss14_auth_uri.to_string(),
     )?);
 
+    let game_servers = config_get!("game_servers.servers", as_array)
+        .map(|servers| {
+            servers
+                .iter()
+                .filter_map(|server| {
+                    Some(GameServer {
+                        name: server.get_path("name")?.as_str()?.to_string(),
+                        url: server.get_path("url")?.as_str()?.to_string(),
+                        admin_token: server
+                            .get_path("admin_token")
+                            .and_then(|t| t.as_str())
+                            .filter(|t| !t.is_empty())
+                            .map(str::to_string),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    container.register(GameServerService::new(game_servers)?);
+
+    let watchdog_url = config_get!("watchdog.url", as_str).unwrap_or_default();
+    let watchdog_instances = config_get!("watchdog.instances", as_array)
+        .map(|instances| {
+            instances
+                .iter()
+                .filter_map(|instance| {
+                    Some(WatchdogInstance {
+                        key: instance.get_path("key")?.as_str()?.to_string(),
+                        token: instance.get_path("token")?.as_str()?.to_string(),
+                        server: instance
+                            .get_path("server")
+                            .and_then(|s| s.as_str())
+                            .map(str::to_string),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    container.register(WatchdogService::new(
+        watchdog_url.to_string(),
+        watchdog_instances,
+    )?);
+
     Ok(())
 }
 
@@ -44,5 +90,55 @@ pub fn command_definitions(
         Arc::new(UserIdCommand::new(services)),
         Arc::new(SummonCommand::new(services)),
         Arc::new(LinkCommand::new(services)),
+        Arc::new(BanCommand::new(services)),
+        Arc::new(RoleBanCommand::new(services)),
+        Arc::new(WhitelistCommand::new(services)),
+        Arc::new(ApplyCommand::new(services)),
+        Arc::new(NotesCommand::new(services)),
+        Arc::new(PlayerCommand::new(services)),
+        Arc::new(PlaytimeCommand::new(services)),
+        Arc::new(LeaderboardCommand::new(services)),
+        Arc::new(RoundCommand::new(services)),
+        Arc::new(RuleCommand),
+        Arc::new(AdminLogCommand::new(services)),
+        Arc::new(CharacterCommand::new(services)),
+        Arc::new(CharactersCommand::new(services)),
+        Arc::new(AdminSyncCommand::new(services)),
+        Arc::new(StaffCommand::new(services)),
+        Arc::new(StatusCommand::new(services)),
+        Arc::new(StatsCommand::new(services)),
+        Arc::new(ServerCommand::new(services)),
+        Arc::new(WatchdogCommand::new(services)),
     ]
 }
+
+pub fn task_definitions(
+    services: &services::ServicesContainer,
+) -> Vec<std::sync::Arc<dyn bot::tasks::DiscordTaskHandler + Send + Sync>> {
+    use bot::tasks::*;
+    use std::sync::Arc;
+
+    vec![
+        Arc::new(ConnectionWatcherTask::new(services)),
+        Arc::new(BanFeedTask::new(services)),
+        Arc::new(PlaytimeRolesTask::new(services)),
+        Arc::new(AdminSyncTask::new(services)),
+        Arc::new(StaffReportTask::new(services)),
+        Arc::new(ServerStatusTask::new(services)),
+        Arc::new(StatsPruneTask::new(services)),
+        Arc::new(ChangelogTask::new(services)),
+    ]
+}
+
+pub fn endpoint_definitions(
+    services: &services::ServicesContainer,
+) -> Result<Vec<std::sync::Arc<dyn bot::endpoints::DiscordEndpointHandler + Send + Sync>>, Error> {
+    use bot::endpoints::*;
+    use std::sync::Arc;
+
+    Ok(vec![
+        Arc::new(AhelpEndpoint::new(services)?),
+        Arc::new(OocEndpoint::new(services)?),
+        Arc::new(RoundEndpoint::new(services)),
+    ])
+}
@@ -44,7 +44,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     log_runtime(&cfg_path);
 
-    let bot = ultor::DiscordApp::new(ultor::command_definitions(&container), &container)?;
+    let bot = ultor::DiscordApp::new(
+        ultor::command_definitions(&container),
+        ultor::task_definitions(&container),
+        ultor::endpoint_definitions(&container)?,
+        &container,
+    )?;
     bot.start().await?;
 
     Ok(())
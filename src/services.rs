@@ -1,10 +1,14 @@
 mod auth_client_service;
 mod bot_db_service;
+mod game_server_service;
 mod ss14_database_service;
+mod watchdog_service;
 
 pub use auth_client_service::*;
 pub use bot_db_service::*;
+pub use game_server_service::*;
 pub use ss14_database_service::*;
+pub use watchdog_service::*;
 
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
@@ -1,11 +1,11 @@
 use crate::error::Error;
 use sqlx::migrate::Migrator;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
-use sqlx::SqlitePool;
+use sqlx::{Row, SqlitePool};
 use std::path::PathBuf;
+use uuid::Uuid;
 #[derive(Debug)]
 pub struct BotDatabaseService {
-    #[allow(dead_code)]
     inner: SqlitePool,
 }
 
@@ -26,6 +26,106 @@ impl BotDatabaseService {
         Ok(Self { inner: pool })
     }
 
-    // implement your own methods here
     // if you want to modify database structure -> look at migrations directory at the root of the project
+
+    /// Last processed row ID or timestamp stored under `name`, used by pollers to resume after restarts.
+    pub async fn get_high_water_mark(&self, name: &str) -> Result<Option<i64>, Error> {
+        let row = sqlx::query("SELECT value FROM high_water_marks WHERE name = ?")
+            .bind(name)
+            .fetch_optional(&self.inner)
+            .await?;
+
+        Ok(row.map(|row| row.get(0)))
+    }
+
+    pub async fn set_high_water_mark(&self, name: &str, value: i64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO high_water_marks (name, value) VALUES (?, ?) \
+             ON CONFLICT (name) DO UPDATE SET value = excluded.value",
+        )
+        .bind(name)
+        .bind(value)
+        .execute(&self.inner)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records player count of `server` at unix `time`.
+    pub async fn add_player_count_sample(
+        &self,
+        server: &str,
+        time: i64,
+        players: i64,
+    ) -> Result<(), Error> {
+        sqlx::query("INSERT INTO player_count_samples (server, time, players) VALUES (?, ?, ?)")
+            .bind(server)
+            .bind(time)
+            .bind(players)
+            .execute(&self.inner)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Player counts since unix `since`, summed over servers per poll, oldest first.
+    pub async fn get_player_count_samples(&self, since: i64) -> Result<Vec<(i64, i64)>, Error> {
+        let rows = sqlx::query(
+            "SELECT time, SUM(players) FROM player_count_samples \
+             WHERE time >= ? \
+             GROUP BY time \
+             ORDER BY time",
+        )
+        .bind(since)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect())
+    }
+
+    /// Deletes samples older than unix `before`. Returns the number of deleted samples.
+    pub async fn prune_player_count_samples(&self, before: i64) -> Result<u64, Error> {
+        let result = sqlx::query("DELETE FROM player_count_samples WHERE time < ?")
+            .bind(before)
+            .execute(&self.inner)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Discord thread relaying ahelps of the SS14 player `user_id`.
+    pub async fn get_ahelp_thread(&self, user_id: Uuid) -> Result<Option<u64>, Error> {
+        let row = sqlx::query("SELECT thread_id FROM ahelp_threads WHERE user_id = ?")
+            .bind(user_id.to_string())
+            .fetch_optional(&self.inner)
+            .await?;
+
+        Ok(row.map(|row| row.get::<i64, _>(0) as u64))
+    }
+
+    /// SS14 player whose ahelps are relayed to `thread_id`.
+    pub async fn get_ahelp_player(&self, thread_id: u64) -> Result<Option<Uuid>, Error> {
+        let row = sqlx::query("SELECT user_id FROM ahelp_threads WHERE thread_id = ?")
+            .bind(thread_id as i64)
+            .fetch_optional(&self.inner)
+            .await?;
+
+        Ok(row.and_then(|row| row.get::<String, _>(0).parse().ok()))
+    }
+
+    pub async fn set_ahelp_thread(&self, user_id: Uuid, thread_id: u64) -> Result<(), Error> {
+        sqlx::query(
+            "INSERT INTO ahelp_threads (user_id, thread_id) VALUES (?, ?) \
+             ON CONFLICT (user_id) DO UPDATE SET thread_id = excluded.thread_id",
+        )
+        .bind(user_id.to_string())
+        .bind(thread_id as i64)
+        .execute(&self.inner)
+        .await?;
+
+        Ok(())
+    }
 }
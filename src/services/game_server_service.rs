@@ -0,0 +1,254 @@
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+static REQWEST_TIMEOUT: u64 = 5;
+
+/// SS14 game server from `game_servers.servers`.
+#[derive(Debug, Clone)]
+pub struct GameServer {
+    pub name: String,
+    pub url: String,
+    /// Token of the game server admin API, admin actions are unavailable without it.
+    pub admin_token: Option<String>,
+}
+
+/// Admin performing an action through the game server admin API.
+#[derive(Debug, Serialize)]
+pub struct AdminActor {
+    #[serde(rename = "Guid")]
+    pub user_id: Uuid,
+    #[serde(rename = "Name")]
+    pub name: String,
+}
+
+/// Round state reported by the game server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunLevel {
+    Lobby,
+    InRound,
+    PostRound,
+    Unknown,
+}
+
+impl RunLevel {
+    fn from_i32(value: i32) -> Self {
+        match value {
+            0 => Self::Lobby,
+            1 => Self::InRound,
+            2 => Self::PostRound,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Lobby => "Lobby",
+            Self::InRound => "In round",
+            Self::PostRound => "Round end",
+            Self::Unknown => "Unknown",
+        }
+    }
+}
+
+/// Response of the game server `/status` endpoint.
+#[derive(Debug, Clone)]
+pub struct ServerStatus {
+    pub players: i32,
+    pub max_players: i32,
+    pub map: Option<String>,
+    pub round_id: Option<i32>,
+    pub run_level: RunLevel,
+}
+
+#[derive(Debug)]
+pub struct GameServerService {
+    inner: reqwest::Client,
+    servers: Vec<GameServer>,
+    /// Last successful poll of every server, `None` if it didn't respond.
+    statuses: RwLock<HashMap<String, Option<ServerStatus>>>,
+}
+
+impl GameServerService {
+    pub fn new(servers: Vec<GameServer>) -> Result<Self, crate::error::Error> {
+        let client = reqwest::ClientBuilder::new()
+            .timeout(Duration::from_secs(REQWEST_TIMEOUT))
+            .build()?;
+
+        Ok(Self {
+            inner: client,
+            servers,
+            statuses: RwLock::new(HashMap::new()),
+        })
+    }
+
+    pub fn servers(&self) -> &[GameServer] {
+        &self.servers
+    }
+
+    pub async fn get_status(
+        &self,
+        server: &GameServer,
+    ) -> Result<ServerStatus, crate::error::Error> {
+        #[derive(Deserialize)]
+        struct JsonResponseBody {
+            players: i32,
+            soft_max_players: i32,
+            map: Option<String>,
+            round_id: Option<i32>,
+            run_level: Option<i32>,
+        }
+
+        let body = self
+            .inner
+            .get(format!("{}/status", server.url.trim_end_matches('/')))
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<JsonResponseBody>()
+            .await?;
+
+        Ok(ServerStatus {
+            players: body.players,
+            max_players: body.soft_max_players,
+            map: body.map,
+            round_id: body.round_id,
+            run_level: body
+                .run_level
+                .map(RunLevel::from_i32)
+                .unwrap_or(RunLevel::Unknown),
+        })
+    }
+
+    /// Polls every server and caches the results. Unreachable servers are cached as `None`.
+    pub async fn poll(&self) -> Vec<(GameServer, Option<ServerStatus>)> {
+        let mut result = Vec::with_capacity(self.servers.len());
+        for server in &self.servers {
+            let status = match self.get_status(server).await {
+                Ok(status) => Some(status),
+                Err(e) => {
+                    warn!("Failed to get status of {}: {}", server.name, e);
+                    None
+                }
+            };
+            result.push((server.clone(), status));
+        }
+
+        let mut statuses = self.statuses.write().await;
+        for (server, status) in &result {
+            statuses.insert(server.name.clone(), status.clone());
+        }
+
+        result
+    }
+
+    /// Status from the last poll, `None` if the server was never polled or didn't respond.
+    pub async fn cached_status(&self, server: &str) -> Option<ServerStatus> {
+        self.statuses.read().await.get(server).cloned().flatten()
+    }
+
+    pub fn find_server(&self, name: &str) -> Option<&GameServer> {
+        self.servers
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Raw `/admin/info` response, shape depends on the game server version.
+    pub async fn admin_info(
+        &self,
+        server: &GameServer,
+        actor: &AdminActor,
+    ) -> Result<Value, crate::error::Error> {
+        let body = self
+            .admin_request(reqwest::Method::GET, server, actor, "/admin/info")?
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Value>()
+            .await?;
+
+        Ok(body)
+    }
+
+    pub async fn announce(
+        &self,
+        server: &GameServer,
+        actor: &AdminActor,
+        message: &str,
+    ) -> Result<(), crate::error::Error> {
+        self.admin_post(
+            server,
+            actor,
+            "/admin/actions/announce",
+            json!({ "Message": message }),
+        )
+        .await
+    }
+
+    pub async fn kick(
+        &self,
+        server: &GameServer,
+        actor: &AdminActor,
+        user_id: Uuid,
+        reason: &str,
+    ) -> Result<(), crate::error::Error> {
+        self.admin_post(
+            server,
+            actor,
+            "/admin/actions/kick",
+            json!({ "Guid": user_id, "Reason": reason }),
+        )
+        .await
+    }
+
+    pub async fn restart_round(
+        &self,
+        server: &GameServer,
+        actor: &AdminActor,
+    ) -> Result<(), crate::error::Error> {
+        self.admin_post(server, actor, "/admin/actions/round/restartnow", json!({}))
+            .await
+    }
+
+    async fn admin_post(
+        &self,
+        server: &GameServer,
+        actor: &AdminActor,
+        path: &str,
+        body: Value,
+    ) -> Result<(), crate::error::Error> {
+        self.admin_request(reqwest::Method::POST, server, actor, path)?
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Builds an admin API request authenticated with the server token and carrying the actor.
+    fn admin_request(
+        &self,
+        method: reqwest::Method,
+        server: &GameServer,
+        actor: &AdminActor,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder, crate::error::Error> {
+        let token = server.admin_token.as_deref().ok_or_else(|| {
+            crate::error::Error::bot(&format!("{} has no admin API token", server.name))
+        })?;
+
+        Ok(self
+            .inner
+            .request(
+                method,
+                format!("{}{}", server.url.trim_end_matches('/'), path),
+            )
+            .header("Authorization", format!("SS14Token {}", token))
+            .header("Actor", serde_json::to_string(actor)?))
+    }
+}
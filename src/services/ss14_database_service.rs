@@ -1,11 +1,226 @@
-use sqlx::{PgPool, Row};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::types::PgInterval;
+use sqlx::{PgExecutor, PgPool, Row};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// Severity stored for bans issued through the bot.
+const BAN_SEVERITY: i32 = NoteSeverity::High as i32;
+
 #[derive(Debug)]
 pub struct SS14DatabaseService {
     inner: PgPool,
 }
 
+#[derive(Debug)]
+pub struct RoleBan {
+    pub id: i32,
+    pub role_id: String,
+    pub reason: String,
+    pub ban_time: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+}
+
+/// Mirrors `NoteSeverity` from SS14.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteSeverity {
+    None = 0,
+    Minor = 1,
+    Medium = 2,
+    High = 3,
+}
+
+impl NoteSeverity {
+    pub fn from_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::None),
+            1 => Some(Self::Minor),
+            2 => Some(Self::Medium),
+            3 => Some(Self::High),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Minor => "Minor",
+            Self::Medium => "Medium",
+            Self::High => "High",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemarkKind {
+    Note,
+    Message,
+    Watchlist,
+}
+
+/// Entry of `admin_notes`, `admin_messages` or `admin_watchlists`.
+#[derive(Debug)]
+pub struct AdminRemark {
+    pub kind: RemarkKind,
+    pub id: i32,
+    pub message: String,
+    /// Only notes have severity.
+    pub severity: Option<NoteSeverity>,
+    pub secret: bool,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+}
+
+/// Account sharing connection identifiers with another one.
+#[derive(Debug)]
+pub struct AltAccount {
+    pub user_id: Uuid,
+    pub login: Option<String>,
+    /// Addresses in textual form, as returned by `host()`.
+    pub shared_addresses: Vec<String>,
+    pub shared_hwids: Vec<Vec<u8>>,
+    pub banned: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BanKind {
+    Server,
+    Role,
+}
+
+impl BanKind {
+    fn table(&self) -> &'static str {
+        match self {
+            Self::Server => "server_ban",
+            Self::Role => "server_role_ban",
+        }
+    }
+
+    fn id_column(&self) -> &'static str {
+        match self {
+            Self::Server => "server_ban_id",
+            Self::Role => "server_role_ban_id",
+        }
+    }
+}
+
+/// Entry of `server_ban` or `server_role_ban`.
+#[derive(Debug)]
+pub struct BanRecord {
+    pub id: i32,
+    pub player_user_id: Option<Uuid>,
+    pub player_login: Option<String>,
+    pub admin_login: Option<String>,
+    pub reason: String,
+    pub ban_time: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub round_id: Option<i32>,
+    pub hidden: bool,
+    /// Only present for role bans.
+    pub role_id: Option<String>,
+}
+
+/// Entry of `connection_log`.
+#[derive(Debug)]
+pub struct Connection {
+    pub id: i32,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub time: DateTime<Utc>,
+    pub address: String,
+    pub hwid: Option<Vec<u8>>,
+    pub denied: bool,
+    pub server_name: Option<String>,
+}
+
+impl AltAccount {
+    fn new(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            login: None,
+            shared_addresses: vec![],
+            shared_hwids: vec![],
+            banned: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NewAdminRemark<'a> {
+    pub kind: RemarkKind,
+    pub player: Uuid,
+    pub message: &'a str,
+    /// Only stored for notes.
+    pub severity: NoteSeverity,
+    /// Only stored for notes.
+    pub secret: bool,
+    pub expiration_time: Option<DateTime<Utc>>,
+}
+
+/// Entry of `round`. SS14 does not store round end or map, so the last admin log entry
+/// is the closest thing to an end time.
+#[derive(Debug)]
+pub struct Round {
+    pub id: i32,
+    pub start_date: Option<DateTime<Utc>>,
+    pub server_name: Option<String>,
+    pub player_count: i64,
+    pub last_log_date: Option<DateTime<Utc>>,
+}
+
+/// Entry of `admin_log`.
+#[derive(Debug)]
+pub struct AdminLogEntry {
+    pub id: i32,
+    pub log_type: i32,
+    pub impact: i16,
+    pub date: DateTime<Utc>,
+    pub message: String,
+}
+
+/// Character slot from `profile`.
+#[derive(Debug)]
+pub struct Character {
+    pub user_id: Uuid,
+    pub login: Option<String>,
+    pub slot: i32,
+    pub name: String,
+    pub species: String,
+    pub age: i32,
+}
+
+/// Activity of a single admin, used by staff reports.
+#[derive(Debug)]
+pub struct StaffActivity {
+    pub user_id: Uuid,
+    pub login: Option<String>,
+    /// Admin log entries the admin is a subject of. SS14 doesn't record who authored an entry,
+    /// but admin actions list the acting admin among their players.
+    pub log_entries: i64,
+    pub bans: i64,
+    pub notes: i64,
+    /// Total time in admin playtime trackers. `play_time` is cumulative, so it is not limited by period.
+    pub admin_playtime: i64,
+}
+
+#[derive(Debug)]
+pub struct AdminLogFilter<'a> {
+    pub round_id: i32,
+    pub log_type: Option<i32>,
+    pub player: Option<Uuid>,
+    /// Case-insensitive substring of the message.
+    pub text: Option<&'a str>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PardonResult {
+    Pardoned,
+    NotFound,
+    AlreadyPardoned,
+    MissingFlag,
+}
+
 impl SS14DatabaseService {
     pub fn new(pg_url: String) -> Result<Self, crate::error::Error> {
         let pg_pool = PgPool::connect_lazy(pg_url.as_str())?;
@@ -27,4 +242,995 @@ impl SS14DatabaseService {
         let user_id: String = row.get(0);
         Ok(Some(user_id))
     }
+
+    /// Inserts a server ban on behalf of `admin`.
+    ///
+    /// Returns `None` if `admin` doesn't hold the `BAN` flag, otherwise the new ban ID.
+    pub async fn add_server_ban(
+        &self,
+        admin: Uuid,
+        player: Uuid,
+        reason: &str,
+        expiration_time: Option<DateTime<Utc>>,
+    ) -> Result<Option<i32>, crate::error::Error> {
+        let mut tx = self.inner.begin().await?;
+
+        if !admin_flags(&mut *tx, admin)
+            .await?
+            .iter()
+            .any(|f| f == "BAN")
+        {
+            return Ok(None);
+        }
+
+        let playtime = overall_playtime(&mut *tx, player).await?;
+
+        let row = sqlx::query(
+            "INSERT INTO server_ban \
+                (player_user_id, ban_time, expiration_time, reason, banning_admin, \
+                 playtime_at_note, severity, auto_delete, hidden, exempt_flags) \
+             VALUES ($1, now(), $2, $3, $4, $5, $6, FALSE, FALSE, 0) \
+             RETURNING server_ban_id",
+        )
+        .bind(player)
+        .bind(expiration_time)
+        .bind(reason)
+        .bind(admin)
+        .bind(playtime)
+        .bind(BAN_SEVERITY)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(row.get(0)))
+    }
+
+    /// Lifts a server ban on behalf of `admin`, who must hold the `BAN` flag.
+    pub async fn pardon_server_ban(
+        &self,
+        admin: Uuid,
+        ban_id: i32,
+    ) -> Result<PardonResult, crate::error::Error> {
+        let mut tx = self.inner.begin().await?;
+
+        if !admin_flags(&mut *tx, admin)
+            .await?
+            .iter()
+            .any(|f| f == "BAN")
+        {
+            return Ok(PardonResult::MissingFlag);
+        }
+
+        let row = sqlx::query(
+            "SELECT b.server_ban_id, u.unban_id FROM server_ban b \
+             LEFT JOIN server_unban u ON u.ban_id = b.server_ban_id \
+             WHERE b.server_ban_id = $1 \
+             FOR UPDATE OF b",
+        )
+        .bind(ban_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(PardonResult::NotFound);
+        };
+
+        if row.get::<Option<i32>, _>(1).is_some() {
+            return Ok(PardonResult::AlreadyPardoned);
+        }
+
+        sqlx::query(
+            "INSERT INTO server_unban (ban_id, unbanning_admin, unban_time) VALUES ($1, $2, now())",
+        )
+        .bind(ban_id)
+        .bind(admin)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(PardonResult::Pardoned)
+    }
+
+    /// Inserts one role ban per entry of `role_ids` on behalf of `admin`.
+    ///
+    /// Returns `None` if `admin` doesn't hold the `BAN` flag, otherwise IDs of the new bans.
+    pub async fn add_role_bans(
+        &self,
+        admin: Uuid,
+        player: Uuid,
+        role_ids: &[String],
+        reason: &str,
+        expiration_time: Option<DateTime<Utc>>,
+    ) -> Result<Option<Vec<i32>>, crate::error::Error> {
+        let mut tx = self.inner.begin().await?;
+
+        if !admin_flags(&mut *tx, admin)
+            .await?
+            .iter()
+            .any(|f| f == "BAN")
+        {
+            return Ok(None);
+        }
+
+        let playtime = overall_playtime(&mut *tx, player).await?;
+        let mut ids = Vec::with_capacity(role_ids.len());
+
+        for role_id in role_ids {
+            let row = sqlx::query(
+                "INSERT INTO server_role_ban \
+                    (player_user_id, ban_time, expiration_time, reason, banning_admin, \
+                     playtime_at_note, severity, hidden, role_id) \
+                 VALUES ($1, now(), $2, $3, $4, $5, $6, FALSE, $7) \
+                 RETURNING server_role_ban_id",
+            )
+            .bind(player)
+            .bind(expiration_time)
+            .bind(reason)
+            .bind(admin)
+            .bind(playtime)
+            .bind(BAN_SEVERITY)
+            .bind(role_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            ids.push(row.get(0));
+        }
+
+        tx.commit().await?;
+        Ok(Some(ids))
+    }
+
+    /// Lifts every active role ban of `player` matching `role_ids` on behalf of `admin`.
+    ///
+    /// Returns `None` if `admin` doesn't hold the `BAN` flag, otherwise the amount of lifted bans.
+    pub async fn remove_role_bans(
+        &self,
+        admin: Uuid,
+        player: Uuid,
+        role_ids: &[String],
+    ) -> Result<Option<u64>, crate::error::Error> {
+        let mut tx = self.inner.begin().await?;
+
+        if !admin_flags(&mut *tx, admin)
+            .await?
+            .iter()
+            .any(|f| f == "BAN")
+        {
+            return Ok(None);
+        }
+
+        let result = sqlx::query(
+            "INSERT INTO server_role_unban (ban_id, unbanning_admin, unban_time) \
+             SELECT b.server_role_ban_id, $2, now() FROM server_role_ban b \
+             WHERE b.player_user_id = $1 AND b.role_id = ANY($3) \
+             AND (b.expiration_time IS NULL OR b.expiration_time > now()) \
+             AND NOT EXISTS (SELECT 1 FROM server_role_unban u WHERE u.ban_id = b.server_role_ban_id)",
+        )
+        .bind(player)
+        .bind(admin)
+        .bind(role_ids)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(result.rows_affected()))
+    }
+
+    /// Lists notes, messages and watchlists of `player` which are neither deleted nor expired,
+    /// newest first.
+    pub async fn get_admin_remarks(
+        &self,
+        player: Uuid,
+    ) -> Result<Vec<AdminRemark>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT 'note', n.admin_notes_id, n.message, n.severity, n.secret, \
+                    p.last_seen_user_name, n.created_at, n.expiration_time \
+             FROM admin_notes n LEFT JOIN player p ON p.user_id = n.created_by_id \
+             WHERE n.player_user_id = $1 AND NOT n.deleted \
+             AND (n.expiration_time IS NULL OR n.expiration_time > now()) \
+             UNION ALL \
+             SELECT 'message', m.admin_messages_id, m.message, NULL, FALSE, \
+                    p.last_seen_user_name, m.created_at, m.expiration_time \
+             FROM admin_messages m LEFT JOIN player p ON p.user_id = m.created_by_id \
+             WHERE m.player_user_id = $1 AND NOT m.deleted \
+             AND (m.expiration_time IS NULL OR m.expiration_time > now()) \
+             UNION ALL \
+             SELECT 'watchlist', w.admin_watchlists_id, w.message, NULL, FALSE, \
+                    p.last_seen_user_name, w.created_at, w.expiration_time \
+             FROM admin_watchlists w LEFT JOIN player p ON p.user_id = w.created_by_id \
+             WHERE w.player_user_id = $1 AND NOT w.deleted \
+             AND (w.expiration_time IS NULL OR w.expiration_time > now()) \
+             ORDER BY 7 DESC",
+        )
+        .bind(player)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| AdminRemark {
+                kind: match row.get::<&str, _>(0) {
+                    "note" => RemarkKind::Note,
+                    "message" => RemarkKind::Message,
+                    _ => RemarkKind::Watchlist,
+                },
+                id: row.get(1),
+                message: row.get(2),
+                severity: row
+                    .get::<Option<i32>, _>(3)
+                    .and_then(NoteSeverity::from_i32),
+                secret: row.get(4),
+                created_by: row.get(5),
+                created_at: row.get(6),
+                expiration_time: row.get(7),
+            })
+            .collect())
+    }
+
+    /// Inserts a note, message or watchlist entry on behalf of `admin`.
+    ///
+    /// Returns `None` if `admin` doesn't hold the `EDITNOTES` flag, otherwise the new entry ID.
+    pub async fn add_admin_remark(
+        &self,
+        admin: Uuid,
+        remark: NewAdminRemark<'_>,
+    ) -> Result<Option<i32>, crate::error::Error> {
+        let mut tx = self.inner.begin().await?;
+
+        if !admin_flags(&mut *tx, admin)
+            .await?
+            .iter()
+            .any(|f| f == "EDITNOTES")
+        {
+            return Ok(None);
+        }
+
+        let playtime = overall_playtime(&mut *tx, remark.player).await?;
+        let query = match remark.kind {
+            RemarkKind::Note => sqlx::query(
+                "INSERT INTO admin_notes \
+                    (player_user_id, playtime_at_note, message, created_by_id, created_at, \
+                     last_edited_by_id, last_edited_at, expiration_time, deleted, severity, secret) \
+                 VALUES ($1, $2, $3, $4, now(), $4, now(), $5, FALSE, $6, $7) \
+                 RETURNING admin_notes_id",
+            ),
+            RemarkKind::Message => sqlx::query(
+                "INSERT INTO admin_messages \
+                    (player_user_id, playtime_at_note, message, created_by_id, created_at, \
+                     last_edited_by_id, last_edited_at, expiration_time, deleted, seen, dismissed) \
+                 VALUES ($1, $2, $3, $4, now(), $4, now(), $5, FALSE, FALSE, FALSE) \
+                 RETURNING admin_messages_id",
+            ),
+            RemarkKind::Watchlist => sqlx::query(
+                "INSERT INTO admin_watchlists \
+                    (player_user_id, playtime_at_note, message, created_by_id, created_at, \
+                     last_edited_by_id, last_edited_at, expiration_time, deleted) \
+                 VALUES ($1, $2, $3, $4, now(), $4, now(), $5, FALSE) \
+                 RETURNING admin_watchlists_id",
+            ),
+        };
+
+        let mut query = query
+            .bind(remark.player)
+            .bind(playtime)
+            .bind(remark.message)
+            .bind(admin)
+            .bind(remark.expiration_time);
+        if remark.kind == RemarkKind::Note {
+            query = query.bind(remark.severity as i32).bind(remark.secret);
+        }
+
+        let row = query.fetch_one(&mut *tx).await?;
+
+        tx.commit().await?;
+        Ok(Some(row.get(0)))
+    }
+
+    /// Finds accounts which connected from the same address or HWID as `user_id` since `since`.
+    pub async fn find_alts(
+        &self,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AltAccount>, crate::error::Error> {
+        let address_rows = sqlx::query(
+            "SELECT DISTINCT c.user_id, host(c.address) FROM connection_log c \
+             JOIN connection_log t ON t.address = c.address \
+             WHERE t.user_id = $1 AND c.user_id <> $1 AND t.time > $2 AND c.time > $2",
+        )
+        .bind(user_id)
+        .bind(since)
+        .fetch_all(&self.inner)
+        .await?;
+
+        let hwid_rows = sqlx::query(
+            "SELECT DISTINCT c.user_id, c.hwid FROM connection_log c \
+             JOIN connection_log t ON t.hwid = c.hwid \
+             WHERE t.user_id = $1 AND c.user_id <> $1 AND t.time > $2 AND c.time > $2 \
+             AND c.hwid IS NOT NULL AND length(c.hwid) > 0",
+        )
+        .bind(user_id)
+        .bind(since)
+        .fetch_all(&self.inner)
+        .await?;
+
+        let mut alts: HashMap<Uuid, AltAccount> = HashMap::new();
+        for row in address_rows {
+            let id: Uuid = row.get(0);
+            alts.entry(id)
+                .or_insert_with(|| AltAccount::new(id))
+                .shared_addresses
+                .push(row.get(1));
+        }
+        for row in hwid_rows {
+            let id: Uuid = row.get(0);
+            alts.entry(id)
+                .or_insert_with(|| AltAccount::new(id))
+                .shared_hwids
+                .push(row.get(1));
+        }
+
+        let mut alts: Vec<AltAccount> = alts.into_values().collect();
+        if alts.is_empty() {
+            return Ok(alts);
+        }
+
+        let ids: Vec<Uuid> = alts.iter().map(|a| a.user_id).collect();
+        let logins =
+            sqlx::query("SELECT user_id, last_seen_user_name FROM player WHERE user_id = ANY($1)")
+                .bind(&ids)
+                .fetch_all(&self.inner)
+                .await?;
+        for row in logins {
+            let id: Uuid = row.get(0);
+            if let Some(alt) = alts.iter_mut().find(|a| a.user_id == id) {
+                alt.login = Some(row.get(1));
+            }
+        }
+
+        for banned in self.get_banned_among(&ids).await? {
+            if let Some(alt) = alts.iter_mut().find(|a| a.user_id == banned) {
+                alt.banned = true;
+            }
+        }
+
+        alts.sort_by(|a, b| a.login.cmp(&b.login));
+        Ok(alts)
+    }
+
+    pub async fn get_latest_connection_id(&self) -> Result<Option<i32>, crate::error::Error> {
+        let row = sqlx::query("SELECT MAX(connection_log_id) FROM connection_log")
+            .fetch_one(&self.inner)
+            .await?;
+
+        Ok(row.get(0))
+    }
+
+    /// Fetches at most `limit` connections with ID greater than `after_id`, oldest first.
+    pub async fn get_connections_after(
+        &self,
+        after_id: i32,
+        limit: i64,
+    ) -> Result<Vec<Connection>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT c.connection_log_id, c.user_id, c.user_name, c.time, host(c.address), \
+                    c.hwid, c.denied IS NOT NULL, s.name \
+             FROM connection_log c LEFT JOIN server s ON s.server_id = c.server_id \
+             WHERE c.connection_log_id > $1 \
+             ORDER BY c.connection_log_id \
+             LIMIT $2",
+        )
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Connection {
+                id: row.get(0),
+                user_id: row.get(1),
+                user_name: row.get(2),
+                time: row.get(3),
+                address: row.get(4),
+                hwid: row.get(5),
+                denied: row.get(6),
+                server_name: row.get(7),
+            })
+            .collect())
+    }
+
+    /// Finds banned accounts which connected from the address or HWID of `connection` since `since`.
+    pub async fn find_banned_alts(
+        &self,
+        connection: &Connection,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Uuid>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT DISTINCT c.user_id FROM connection_log c \
+             WHERE c.user_id <> $1 AND c.time > $4 \
+             AND (c.address = $2::inet OR (c.hwid = $3 AND length(c.hwid) > 0))",
+        )
+        .bind(connection.user_id)
+        .bind(&connection.address)
+        .bind(&connection.hwid)
+        .bind(since)
+        .fetch_all(&self.inner)
+        .await?;
+
+        let user_ids: Vec<Uuid> = rows.into_iter().map(|row| row.get(0)).collect();
+        if user_ids.is_empty() {
+            return Ok(user_ids);
+        }
+
+        self.get_banned_among(&user_ids).await
+    }
+
+    pub async fn get_latest_ban_id(
+        &self,
+        kind: BanKind,
+    ) -> Result<Option<i32>, crate::error::Error> {
+        let row = sqlx::query(&format!(
+            "SELECT MAX({}) FROM {}",
+            kind.id_column(),
+            kind.table()
+        ))
+        .fetch_one(&self.inner)
+        .await?;
+
+        Ok(row.get(0))
+    }
+
+    /// Fetches at most `limit` bans of `kind` with ID greater than `after_id`, oldest first.
+    pub async fn get_bans_after(
+        &self,
+        kind: BanKind,
+        after_id: i32,
+        limit: i64,
+    ) -> Result<Vec<BanRecord>, crate::error::Error> {
+        let role_column = match kind {
+            BanKind::Server => "NULL::text",
+            BanKind::Role => "b.role_id",
+        };
+
+        let rows = sqlx::query(&format!(
+            "SELECT b.{id}, b.player_user_id, p.last_seen_user_name, a.last_seen_user_name, \
+                    b.reason, b.ban_time, b.expiration_time, b.round_id, b.hidden, {role} \
+             FROM {table} b \
+             LEFT JOIN player p ON p.user_id = b.player_user_id \
+             LEFT JOIN player a ON a.user_id = b.banning_admin \
+             WHERE b.{id} > $1 \
+             ORDER BY b.{id} \
+             LIMIT $2",
+            id = kind.id_column(),
+            table = kind.table(),
+            role = role_column,
+        ))
+        .bind(after_id)
+        .bind(limit)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| BanRecord {
+                id: row.get(0),
+                player_user_id: row.get(1),
+                player_login: row.get(2),
+                admin_login: row.get(3),
+                reason: row.get(4),
+                ban_time: row.get(5),
+                expiration_time: row.get(6),
+                round_id: row.get(7),
+                hidden: row.get(8),
+                role_id: row.get(9),
+            })
+            .collect())
+    }
+
+    /// Returns those of `user_ids` who have an active server ban.
+    pub async fn get_banned_among(
+        &self,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT DISTINCT b.player_user_id FROM server_ban b \
+             WHERE b.player_user_id = ANY($1) \
+             AND (b.expiration_time IS NULL OR b.expiration_time > now()) \
+             AND NOT EXISTS (SELECT 1 FROM server_unban u WHERE u.ban_id = b.server_ban_id)",
+        )
+        .bind(user_ids)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.get(0)).collect())
+    }
+
+    /// Lists playtime trackers of `user_id` in seconds, longest first.
+    pub async fn get_playtime(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<(String, i64)>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT tracker, EXTRACT(EPOCH FROM time_spent)::bigint FROM play_time \
+             WHERE player_id = $1 \
+             ORDER BY time_spent DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect())
+    }
+
+    /// Top players by all-time playtime of `tracker` in seconds. `seen_since` limits it to players
+    /// last seen after then, `play_time` keeps no history to rank by playtime within a period.
+    pub async fn get_playtime_leaderboard(
+        &self,
+        tracker: &str,
+        seen_since: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<(Uuid, String, i64)>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT p.user_id, p.last_seen_user_name, EXTRACT(EPOCH FROM t.time_spent)::bigint \
+             FROM play_time t JOIN player p ON p.user_id = t.player_id \
+             WHERE t.tracker = $1 AND ($2::timestamptz IS NULL OR p.last_seen_time > $2) \
+             ORDER BY t.time_spent DESC \
+             LIMIT $3",
+        )
+        .bind(tracker)
+        .bind(seen_since)
+        .bind(limit)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1), row.get(2)))
+            .collect())
+    }
+
+    pub async fn get_round(&self, round_id: i32) -> Result<Option<Round>, crate::error::Error> {
+        let row = sqlx::query(
+            "SELECT r.round_id, r.start_date, s.name, \
+                    (SELECT COUNT(*) FROM player_round pr WHERE pr.rounds_id = r.round_id), \
+                    (SELECT MAX(l.date) FROM admin_log l WHERE l.round_id = r.round_id) \
+             FROM round r LEFT JOIN server s ON s.server_id = r.server_id \
+             WHERE r.round_id = $1",
+        )
+        .bind(round_id)
+        .fetch_optional(&self.inner)
+        .await?;
+
+        Ok(row.map(|row| Round {
+            id: row.get(0),
+            start_date: row.get(1),
+            server_name: row.get(2),
+            player_count: row.get(3),
+            last_log_date: row.get(4),
+        }))
+    }
+
+    /// Searches admin logs of a round, oldest first. Returns the requested page and total match count.
+    pub async fn search_admin_logs(
+        &self,
+        filter: &AdminLogFilter<'_>,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<AdminLogEntry>, i64), crate::error::Error> {
+        const CONDITIONS: &str = "WHERE l.round_id = $1 \
+             AND ($2::integer IS NULL OR l.type = $2) \
+             AND ($3::uuid IS NULL OR EXISTS (SELECT 1 FROM admin_log_player p \
+                 WHERE p.round_id = l.round_id AND p.log_id = l.admin_log_id AND p.player_user_id = $3)) \
+             AND ($4::text IS NULL OR l.message ILIKE '%' || $4 || '%' ESCAPE '\\')";
+
+        let text = filter.text.map(escape_like);
+
+        let total: i64 = sqlx::query(&format!("SELECT COUNT(*) FROM admin_log l {}", CONDITIONS))
+            .bind(filter.round_id)
+            .bind(filter.log_type)
+            .bind(filter.player)
+            .bind(&text)
+            .fetch_one(&self.inner)
+            .await?
+            .get(0);
+
+        let rows = sqlx::query(&format!(
+            "SELECT l.admin_log_id, l.type, l.impact, l.date, l.message FROM admin_log l {} \
+             ORDER BY l.admin_log_id \
+             OFFSET $5 LIMIT $6",
+            CONDITIONS
+        ))
+        .bind(filter.round_id)
+        .bind(filter.log_type)
+        .bind(filter.player)
+        .bind(&text)
+        .bind(offset)
+        .bind(limit)
+        .fetch_all(&self.inner)
+        .await?;
+
+        let entries = rows
+            .into_iter()
+            .map(|row| AdminLogEntry {
+                id: row.get(0),
+                log_type: row.get(1),
+                impact: row.get(2),
+                date: row.get(3),
+                message: row.get(4),
+            })
+            .collect();
+
+        Ok((entries, total))
+    }
+
+    /// Logins starting with `prefix`, case-insensitive, shortest first.
+    pub async fn search_logins_by_prefix(
+        &self,
+        prefix: &str,
+        limit: i64,
+    ) -> Result<Vec<String>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT last_seen_user_name FROM player \
+             WHERE last_seen_user_name ILIKE $1 || '%' ESCAPE '\\' \
+             ORDER BY length(last_seen_user_name), last_seen_user_name \
+             LIMIT $2",
+        )
+        .bind(escape_like(prefix))
+        .bind(limit)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.get(0)).collect())
+    }
+
+    /// Logins similar to `query` by trigram similarity, most similar first.
+    /// Requires the `pg_trgm` extension in the SS14 database.
+    pub async fn search_logins_by_similarity(
+        &self,
+        query: &str,
+        limit: i64,
+    ) -> Result<Vec<String>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT last_seen_user_name FROM player \
+             WHERE last_seen_user_name % $1 \
+             ORDER BY similarity(last_seen_user_name, $1) DESC \
+             LIMIT $2",
+        )
+        .bind(query)
+        .bind(limit)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.get(0)).collect())
+    }
+
+    /// Finds characters whose name contains `name`, exact matches first.
+    pub async fn find_characters(
+        &self,
+        name: &str,
+        limit: i64,
+    ) -> Result<Vec<Character>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT pr.user_id, p.last_seen_user_name, c.slot, c.char_name, c.species, c.age \
+             FROM profile c \
+             JOIN preference pr ON pr.preference_id = c.preference_id \
+             LEFT JOIN player p ON p.user_id = pr.user_id \
+             WHERE c.char_name ILIKE '%' || $1 || '%' ESCAPE '\\' \
+             ORDER BY lower(c.char_name) <> lower($2), c.char_name \
+             LIMIT $3",
+        )
+        .bind(escape_like(name))
+        .bind(name)
+        .bind(limit)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows.into_iter().map(character_from_row).collect())
+    }
+
+    /// Lists every character slot of `user_id`.
+    pub async fn get_characters(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<Character>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT pr.user_id, p.last_seen_user_name, c.slot, c.char_name, c.species, c.age \
+             FROM profile c \
+             JOIN preference pr ON pr.preference_id = c.preference_id \
+             LEFT JOIN player p ON p.user_id = pr.user_id \
+             WHERE pr.user_id = $1 \
+             ORDER BY c.slot",
+        )
+        .bind(user_id)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows.into_iter().map(character_from_row).collect())
+    }
+
+    /// Checks that `user_id` is an active admin holding every flag in `flags`.
+    pub async fn has_admin_flags(
+        &self,
+        user_id: Uuid,
+        flags: &[&str],
+    ) -> Result<bool, crate::error::Error> {
+        if flags.is_empty() {
+            return Ok(true);
+        }
+
+        let held = self.get_admin_flags(user_id).await?;
+        Ok(flags.iter().all(|flag| held.iter().any(|h| h == flag)))
+    }
+
+    /// Effective flags of `user_id`: rank and positive admin flags minus negative ones.
+    /// Empty if the player is not an admin or is suspended.
+    pub async fn get_admin_flags(&self, user_id: Uuid) -> Result<Vec<String>, crate::error::Error> {
+        admin_flags(&self.inner, user_id).await
+    }
+
+    /// Aggregates activity of every admin since `since`, most active first.
+    pub async fn get_staff_activity(
+        &self,
+        since: DateTime<Utc>,
+        admin_trackers: &[String],
+    ) -> Result<Vec<StaffActivity>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT * FROM ( \
+                SELECT a.user_id, p.last_seen_user_name, \
+                       (SELECT COUNT(*) FROM admin_log_player lp \
+                        JOIN admin_log l ON l.round_id = lp.round_id AND l.admin_log_id = lp.log_id \
+                        WHERE lp.player_user_id = a.user_id AND l.date > $1) AS log_entries, \
+                       (SELECT COUNT(*) FROM server_ban b \
+                        WHERE b.banning_admin = a.user_id AND b.ban_time > $1) AS bans, \
+                       (SELECT COUNT(*) FROM admin_notes n \
+                        WHERE n.created_by_id = a.user_id AND n.created_at > $1) AS notes, \
+                       (SELECT COALESCE(EXTRACT(EPOCH FROM SUM(t.time_spent)), 0)::bigint \
+                        FROM play_time t \
+                        WHERE t.player_id = a.user_id AND t.tracker = ANY($2)) AS admin_playtime \
+                FROM admin a LEFT JOIN player p ON p.user_id = a.user_id \
+             ) activity \
+             ORDER BY activity.bans + activity.notes DESC, activity.log_entries DESC",
+        )
+        .bind(since)
+        .bind(admin_trackers)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| StaffActivity {
+                user_id: row.get(0),
+                login: row.get(1),
+                log_entries: row.get(2),
+                bans: row.get(3),
+                notes: row.get(4),
+                admin_playtime: row.get(5),
+            })
+            .collect())
+    }
+
+    /// Lists every in-game admin with the name of their rank.
+    pub async fn get_admin_ranks(
+        &self,
+    ) -> Result<Vec<(Uuid, Option<String>)>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT a.user_id, r.name FROM admin a \
+             LEFT JOIN admin_rank r ON r.admin_rank_id = a.admin_rank_id",
+        )
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect())
+    }
+
+    /// Makes `user_id` an admin with `rank`, or changes the rank of an existing admin.
+    /// Returns `false` if no rank with such name exists.
+    pub async fn set_admin_rank(
+        &self,
+        user_id: Uuid,
+        rank: &str,
+    ) -> Result<bool, crate::error::Error> {
+        let result = sqlx::query(
+            "INSERT INTO admin (user_id, admin_rank_id) \
+             SELECT $1, admin_rank_id FROM admin_rank WHERE name = $2 \
+             ON CONFLICT (user_id) DO UPDATE SET admin_rank_id = EXCLUDED.admin_rank_id",
+        )
+        .bind(user_id)
+        .bind(rank)
+        .execute(&self.inner)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Removes `user_id` from admins along with their individual flags.
+    pub async fn remove_admin(&self, user_id: Uuid) -> Result<bool, crate::error::Error> {
+        let mut tx = self.inner.begin().await?;
+
+        sqlx::query("DELETE FROM admin_flag WHERE admin_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("DELETE FROM admin WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Adds `user_id` to the whitelist. Returns `false` if it was already whitelisted.
+    pub async fn add_to_whitelist(&self, user_id: Uuid) -> Result<bool, crate::error::Error> {
+        let result =
+            sqlx::query("INSERT INTO whitelist (user_id) VALUES ($1) ON CONFLICT DO NOTHING")
+                .bind(user_id)
+                .execute(&self.inner)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Adds every of `user_ids` to the whitelist, returns the amount of newly whitelisted users.
+    pub async fn add_many_to_whitelist(
+        &self,
+        user_ids: &[Uuid],
+    ) -> Result<u64, crate::error::Error> {
+        let result = sqlx::query(
+            "INSERT INTO whitelist (user_id) SELECT * FROM UNNEST($1::uuid[]) ON CONFLICT DO NOTHING",
+        )
+        .bind(user_ids)
+        .execute(&self.inner)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Removes `user_id` from the whitelist. Returns `false` if it wasn't whitelisted.
+    pub async fn remove_from_whitelist(&self, user_id: Uuid) -> Result<bool, crate::error::Error> {
+        let result = sqlx::query("DELETE FROM whitelist WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.inner)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn is_whitelisted(&self, user_id: Uuid) -> Result<bool, crate::error::Error> {
+        let row = sqlx::query("SELECT EXISTS (SELECT 1 FROM whitelist WHERE user_id = $1)")
+            .bind(user_id)
+            .fetch_one(&self.inner)
+            .await?;
+
+        Ok(row.get(0))
+    }
+
+    /// Adds a job whitelist for `user_id`. Returns `false` if it was already present.
+    pub async fn add_job_whitelist(
+        &self,
+        user_id: Uuid,
+        job: &str,
+    ) -> Result<bool, crate::error::Error> {
+        let result = sqlx::query(
+            "INSERT INTO role_whitelists (player_user_id, role_id) VALUES ($1, $2) \
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(user_id)
+        .bind(job)
+        .execute(&self.inner)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Lists whitelisted users along with their last seen login, if any.
+    pub async fn get_whitelist(&self) -> Result<Vec<(Uuid, Option<String>)>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT w.user_id, p.last_seen_user_name FROM whitelist w \
+             LEFT JOIN player p ON p.user_id = w.user_id \
+             ORDER BY p.last_seen_user_name",
+        )
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect())
+    }
+
+    /// Lists active role bans of `player`.
+    pub async fn get_role_bans(&self, player: Uuid) -> Result<Vec<RoleBan>, crate::error::Error> {
+        let rows = sqlx::query(
+            "SELECT b.server_role_ban_id, b.role_id, b.reason, b.ban_time, b.expiration_time \
+             FROM server_role_ban b \
+             WHERE b.player_user_id = $1 \
+             AND (b.expiration_time IS NULL OR b.expiration_time > now()) \
+             AND NOT EXISTS (SELECT 1 FROM server_role_unban u WHERE u.ban_id = b.server_role_ban_id) \
+             ORDER BY b.ban_time DESC",
+        )
+        .bind(player)
+        .fetch_all(&self.inner)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| RoleBan {
+                id: row.get(0),
+                role_id: row.get(1),
+                reason: row.get(2),
+                ban_time: row.get(3),
+                expiration_time: row.get(4),
+            })
+            .collect())
+    }
+}
+
+/// Fetches the `Overall` playtime tracker, which SS14 stores alongside every ban and note.
+async fn overall_playtime<'e, E: PgExecutor<'e>>(
+    executor: E,
+    player: Uuid,
+) -> Result<PgInterval, crate::error::Error> {
+    let row = sqlx::query(
+        "SELECT time_spent FROM play_time WHERE player_id = $1 AND tracker = 'Overall'",
+    )
+    .bind(player)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(row
+        .map(|row| row.get::<PgInterval, _>(0))
+        .unwrap_or(PgInterval {
+            months: 0,
+            days: 0,
+            microseconds: 0,
+        }))
+}
+
+/// Effective flags of `user_id`: rank and positive admin flags minus negative ones, as in game.
+/// Empty if the player is not an admin or is suspended.
+async fn admin_flags<'e, E: PgExecutor<'e>>(
+    executor: E,
+    user_id: Uuid,
+) -> Result<Vec<String>, crate::error::Error> {
+    let rows = sqlx::query(
+        "SELECT f.flag FROM admin a \
+         CROSS JOIN LATERAL ( \
+            SELECT rf.flag FROM admin_rank_flag rf WHERE rf.admin_rank_id = a.admin_rank_id \
+            UNION \
+            SELECT af.flag FROM admin_flag af WHERE af.admin_id = a.user_id AND NOT af.negative \
+         ) f \
+         WHERE a.user_id = $1 AND NOT a.suspended \
+         AND NOT EXISTS (SELECT 1 FROM admin_flag nf \
+                         WHERE nf.admin_id = a.user_id AND nf.flag = f.flag AND nf.negative)",
+    )
+    .bind(user_id)
+    .fetch_all(executor)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.get(0)).collect())
+}
+
+fn character_from_row(row: sqlx::postgres::PgRow) -> Character {
+    Character {
+        user_id: row.get(0),
+        login: row.get(1),
+        slot: row.get(2),
+        name: row.get(3),
+        species: row.get(4),
+        age: row.get(5),
+    }
+}
+
+/// Escapes `LIKE` wildcards so user input is matched literally.
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
 }
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+static REQWEST_TIMEOUT: u64 = 10;
+
+/// Instance managed by SS14.Watchdog, from `watchdog.instances`.
+#[derive(Debug, Clone)]
+pub struct WatchdogInstance {
+    pub key: String,
+    pub token: String,
+    /// Name of the game server from `game_servers.servers` run by this instance.
+    pub server: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct WatchdogService {
+    inner: reqwest::Client,
+    url: String,
+    instances: Vec<WatchdogInstance>,
+}
+
+impl WatchdogService {
+    pub fn new(url: String, instances: Vec<WatchdogInstance>) -> Result<Self, crate::error::Error> {
+        let client = reqwest::ClientBuilder::new()
+            .timeout(Duration::from_secs(REQWEST_TIMEOUT))
+            .build()?;
+
+        Ok(Self {
+            inner: client,
+            url,
+            instances,
+        })
+    }
+
+    pub fn find_instance(&self, key: &str) -> Option<&WatchdogInstance> {
+        self.instances.iter().find(|i| i.key == key)
+    }
+
+    /// Restarts the game server process of `instance`.
+    pub async fn restart(&self, instance: &WatchdogInstance) -> Result<(), crate::error::Error> {
+        self.post(instance, "restart").await
+    }
+
+    /// Makes the watchdog check for a new build and restart `instance` into it.
+    pub async fn update(&self, instance: &WatchdogInstance) -> Result<(), crate::error::Error> {
+        self.post(instance, "update").await
+    }
+
+    async fn post(
+        &self,
+        instance: &WatchdogInstance,
+        action: &str,
+    ) -> Result<(), crate::error::Error> {
+        self.inner
+            .post(format!(
+                "{}/instances/{}/{}",
+                self.url.trim_end_matches('/'),
+                instance.key,
+                action
+            ))
+            .basic_auth(&instance.key, Some(&instance.token))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
@@ -1,15 +1,203 @@
 use rand::Rng;
-use serenity::all::Color;
+use serenity::all::{ChannelId, Color};
 use uuid::Uuid;
 
 use crate::services::SS14AuthClientService;
 
 pub const RED_COLOR: Color = Color::from_rgb(255, 0, 0);
+const DEFAULT_ALTS_LOOKBACK_DAYS: i64 = 90;
 
 pub fn gen_random_uuid() -> Uuid {
     Uuid::from_u128(rand::rng().random::<u128>())
 }
 
+/// Parses durations like `30m`, `12h`, `1w2d`. A bare number is treated as minutes,
+/// negative durations are rejected.
+pub fn parse_duration(s: &str) -> Option<chrono::Duration> {
+    let s = s.trim();
+    if let Ok(minutes) = s.parse::<i64>() {
+        return chrono::Duration::try_minutes(minutes).filter(|_| minutes >= 0);
+    }
+
+    let mut total = chrono::Duration::zero();
+    let mut number = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let value = number.parse::<i64>().ok()?;
+        number.clear();
+        let part = match c {
+            'm' => chrono::Duration::try_minutes(value)?,
+            'h' => chrono::Duration::try_hours(value)?,
+            'd' => chrono::Duration::try_days(value)?,
+            'w' => chrono::Duration::try_weeks(value)?,
+            _ => return None,
+        };
+        total = total.checked_add(&part)?;
+    }
+
+    if !number.is_empty() || total.is_zero() {
+        return None;
+    }
+
+    Some(total)
+}
+
+/// Formats duration as `1w 2d 3h 4m`.
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let mut minutes = duration.num_minutes();
+    if minutes <= 0 {
+        return "0m".to_string();
+    }
+
+    let mut parts = vec![];
+    for (unit, size) in [("w", 60 * 24 * 7), ("d", 60 * 24), ("h", 60), ("m", 1)] {
+        if minutes >= size {
+            parts.push(format!("{}{}", minutes / size, unit));
+            minutes %= size;
+        }
+    }
+
+    parts.join(" ")
+}
+
+/// Formats seconds of playtime as `123h 45m`.
+pub fn format_playtime(seconds: i64) -> String {
+    format!("{}h {}m", seconds / 3600, seconds % 3600 / 60)
+}
+
+/// Playtime trackers with their display names from `playtime.trackers`, sorted by display name.
+pub fn configured_trackers() -> Vec<(String, String)> {
+    let mut trackers: Vec<(String, String)> = crate::config_get!("playtime.trackers", as_object)
+        .map(|trackers| {
+            trackers
+                .iter()
+                .filter_map(|(id, name)| Some((id.clone(), name.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    trackers.sort_by(|a, b| a.1.cmp(&b.1));
+    trackers
+}
+
+/// Display name of a playtime tracker, falls back to the tracker ID.
+pub fn tracker_display_name(tracker: &str) -> String {
+    crate::config_get!("playtime.trackers", as_object)
+        .and_then(|trackers| trackers.get(tracker))
+        .and_then(|name| name.as_str())
+        .unwrap_or(tracker)
+        .to_string()
+}
+
+/// Reads `roles.departments`, mapping department names to job prototype IDs.
+pub fn configured_departments() -> Vec<(String, Vec<String>)> {
+    let Some(departments) = crate::config_get!("roles.departments", as_object) else {
+        return vec![];
+    };
+
+    let mut departments: Vec<(String, Vec<String>)> = departments
+        .iter()
+        .map(|(name, jobs)| {
+            let jobs = jobs
+                .as_array()
+                .map(|jobs| {
+                    jobs.iter()
+                        .filter_map(|job| job.as_str())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            (name.clone(), jobs)
+        })
+        .collect();
+
+    departments.sort_by(|a, b| a.0.cmp(&b.0));
+    departments
+}
+
+/// Draws `values` as a line of block characters scaled to the largest value.
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if max <= 0.0 {
+                return BARS[0];
+            }
+            let index = (value / max * (BARS.len() - 1) as f64).round() as usize;
+            BARS[index.min(BARS.len() - 1)]
+        })
+        .collect()
+}
+
+/// Parses ban duration into an expiration time. `perm` or zero duration produces a permanent ban.
+pub fn parse_ban_expiration(s: &str) -> Option<Option<chrono::DateTime<chrono::Utc>>> {
+    if matches!(s.trim(), "perm" | "permanent") {
+        return Some(None);
+    }
+
+    let duration = parse_duration(s)?;
+    if duration.is_zero() {
+        return Some(None);
+    }
+
+    Some(Some(chrono::Utc::now() + duration))
+}
+
+/// Reads discord channel ID stored as a string at `path`. `"0"` means channel is not configured.
+pub fn config_channel(path: &str) -> Option<ChannelId> {
+    crate::config_get!(path, as_str)
+        .and_then(|id| id.parse::<u64>().ok())
+        .filter(|id| *id != 0)
+        .map(ChannelId::new)
+}
+
+/// Cuts `s` to at most `max` characters, marking the cut with an ellipsis.
+pub fn truncate_chars(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+
+    let mut result: String = s.chars().take(max.saturating_sub(1)).collect();
+    result.push('…');
+    result
+}
+
+/// How far back `connection_log` is searched for accounts sharing identifiers.
+pub fn alts_lookback_days() -> i64 {
+    crate::config_get!("moderation.alts_lookback_days", as_int)
+        .map(i64::from)
+        .unwrap_or(DEFAULT_ALTS_LOOKBACK_DAYS)
+}
+
+/// Masks the host part of an address: `192.168.*.*` for IPv4, first two groups for IPv6.
+pub fn mask_address(address: &str) -> String {
+    if address.contains(':') {
+        let groups: Vec<&str> = address.split(':').take(2).collect();
+        return format!("{}:*", groups.join(":"));
+    }
+
+    let octets: Vec<&str> = address.split('.').take(2).collect();
+    format!("{}.*.*", octets.join("."))
+}
+
+/// Short SHA-256 digest of an identifier, stable enough to compare identifiers without exposing them.
+pub fn hash_identifier(identifier: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(identifier)
+        .iter()
+        .take(6)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
 pub fn gen_random_color() -> Color {
     let mut rng = rand::rng();
     Color::from_rgb(rng.random(), rng.random(), rng.random())
@@ -102,6 +290,20 @@ macro_rules! extract_discord_arg {
     };
 }
 
+/// Resolves SS14 user ID from either in-game login or raw UUID.
+pub async fn resolve_user_id(
+    login_or_uuid: &str,
+    ss14_client: &std::sync::Arc<SS14AuthClientService>,
+) -> Result<Option<Uuid>, crate::error::Error> {
+    if let Ok(user_id) = login_or_uuid.trim().parse::<Uuid>() {
+        return Ok(Some(user_id));
+    }
+
+    ss14_client
+        .get_user_id(login_or_uuid.trim().to_string())
+        .await
+}
+
 pub async fn format_extra_data(
     discord_id: &str,
     ss14_client: &std::sync::Arc<SS14AuthClientService>,
@@ -149,3 +351,61 @@ pub async fn format_extra_data(
         None => Ok("No extra data found.".to_string()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn parses_duration_units() {
+        assert_eq!(parse_duration("30m"), Some(Duration::minutes(30)));
+        assert_eq!(parse_duration("12h"), Some(Duration::hours(12)));
+        assert_eq!(parse_duration("3d"), Some(Duration::days(3)));
+        assert_eq!(parse_duration("2w"), Some(Duration::weeks(2)));
+        assert_eq!(parse_duration(" 1w2d "), Some(Duration::days(9)));
+        assert_eq!(
+            parse_duration("1d12h30m"),
+            Some(Duration::days(1) + Duration::hours(12) + Duration::minutes(30))
+        );
+    }
+
+    #[test]
+    fn parses_bare_number_as_minutes() {
+        assert_eq!(parse_duration("90"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("0"), Some(Duration::zero()));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert_eq!(parse_duration("-5"), None);
+        assert_eq!(parse_duration("-5m"), None);
+        assert_eq!(parse_duration("0m"), None);
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("1w2"), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn rejects_overflowing_durations() {
+        assert_eq!(parse_duration(&i64::MAX.to_string()), None);
+        assert_eq!(parse_duration("99999999999999999999m"), None);
+        assert_eq!(parse_duration("9999999999999w"), None);
+        assert_eq!(parse_duration("9000000000000w9000000000000w"), None);
+    }
+
+    #[test]
+    fn parses_ban_expiration() {
+        assert_eq!(parse_ban_expiration("perm"), Some(None));
+        assert_eq!(parse_ban_expiration("permanent"), Some(None));
+        assert_eq!(parse_ban_expiration("0"), Some(None));
+        assert_eq!(parse_ban_expiration("-5"), None);
+        assert_eq!(parse_ban_expiration("forever"), None);
+
+        let before = chrono::Utc::now();
+        let expiration = parse_ban_expiration("1d").flatten().unwrap();
+        assert!(expiration >= before + Duration::days(1));
+        assert!(expiration <= chrono::Utc::now() + Duration::days(1));
+    }
+}